@@ -1,4 +1,4 @@
-use lightspeedvalidator::{GammaRayAnalyzer, TimingData};
+use lightspeedvalidator::GammaRayAnalyzer;
 
 fn main() {
     // Create analyzer instance
@@ -1,199 +1,2870 @@
 // src/analyzer.rs
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::OnceLock;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::cosmology::Cosmology;
+use crate::stats_math::{stable_sum, CompensatedSum};
+use crate::statistics::{
+    chi_squared_pvalue, percentile_sorted, standard_normal_quantile, student_t_quantile,
+    weighted_linear_fit, weighted_median, LinearFit,
+};
+
+/// Speed of light in vacuum (m/s)
+pub const SPEED_OF_LIGHT: f64 = 299792458.0;
+
+/// Maximum EM iterations for `GammaRayAnalyzer::fit_two_gaussian_mixture`
+const MIXTURE_MAX_ITERATIONS: usize = 1000;
+/// Log-likelihood change below which the mixture fit is considered converged
+const MIXTURE_TOLERANCE: f64 = 1e-10;
+/// Jitter draws tried per tied photon before it is left in place
+const DEDUPLICATION_MAX_DRAWS: usize = 1000;
+/// Maximum reweighting passes for fits under `LossKind::Huber`
+const IRLS_MAX_ITERATIONS: usize = 100;
+/// Parameter change, in units of its standard error, below which reweighting stops
+const IRLS_TOLERANCE: f64 = 1e-10;
+/// Ratio of the standard deviation to the median absolute deviation for Gaussian data
+const MAD_TO_SIGMA: f64 = 1.4826;
+/// Largest number of time bins a light curve may span
+pub const MAX_LIGHT_CURVE_BINS: usize = 10_000_000;
 
 /// Represents a single timing measurement from gamma-ray detection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimingData {
     pub energy: f64,           // Energy in GeV
     pub arrival_time: f64,     // Arrival time in seconds since Unix epoch
     pub error: f64,            // Measurement error in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance_m: Option<f64>, // Source distance in meters, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redshift: Option<f64>,   // Source redshift, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>, // Key into the analyzer's registered sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_low: Option<f64>,  // Uncertainty below the arrival time, if asymmetric
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_high: Option<f64>, // Uncertainty above the arrival time, if asymmetric
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,     // Exposure or effective-area weight; 1 if unset
+}
+
+impl TimingData {
+    /// Creates a measurement with unknown source geometry
+    pub fn new(energy: f64, arrival_time: f64, error: f64) -> Self {
+        Self {
+            energy,
+            arrival_time,
+            error,
+            distance_m: None,
+            redshift: None,
+            source_id: None,
+            error_low: None,
+            error_high: None,
+            weight: None,
+        }
+    }
+
+    /// Tags the measurement with the identifier of its source
+    pub fn with_source(mut self, source_id: impl Into<String>) -> Self {
+        self.source_id = Some(source_id.into());
+        self
+    }
+
+    /// Sets the source distance in meters
+    pub fn with_distance(mut self, distance_m: f64) -> Self {
+        self.distance_m = Some(distance_m);
+        self
+    }
+
+    /// Sets the source redshift
+    pub fn with_redshift(mut self, redshift: f64) -> Self {
+        self.redshift = Some(redshift);
+        self
+    }
+
+    /// Sets separate uncertainties below and above the arrival time
+    ///
+    /// The symmetric `error` is kept for calculations that need a single value.
+    pub fn with_asymmetric_errors(mut self, error_low: f64, error_high: f64) -> Self {
+        self.error_low = Some(error_low);
+        self.error_high = Some(error_high);
+        self
+    }
+
+    /// Sets the exposure or effective-area weight multiplying `1 / error²`
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Statistical weight multiplier, 1 when no weight is set
+    pub fn weight_factor(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    /// Error scaled so that `1 / weighted_error²` is the photon's weighted inverse variance
+    pub fn weighted_error(&self) -> f64 {
+        self.error / self.weight_factor().sqrt()
+    }
+
+    /// Timing uncertainty on the side of the arrival time where the model lies
+    ///
+    /// `residual` is the measured minus the expected arrival. A positive residual
+    /// puts the model below the measurement, so `error_low` applies; a negative one
+    /// uses `error_high`. Missing sides, and a zero residual, fall back to `error`.
+    pub fn error_for_residual(&self, residual: f64) -> f64 {
+        let side = if residual > 0.0 {
+            self.error_low
+        } else if residual < 0.0 {
+            self.error_high
+        } else {
+            None
+        };
+        side.unwrap_or(self.error)
+    }
+
+    /// Geometric light-travel time `distance / c` in seconds, if the distance is known
+    pub fn light_travel_time(&self) -> Option<f64> {
+        self.distance_m.map(|distance| distance / SPEED_OF_LIGHT)
+    }
+
+    /// Checks that the measurement is physical
+    ///
+    /// Every field, including a distance, redshift, asymmetric error or weight when
+    /// present, must be finite; the energy, every measurement error and the weight must
+    /// also be strictly positive.
+    pub fn validate(&self) -> Result<(), TimingDataError> {
+        let fields = [
+            ("energy", Some(self.energy)),
+            ("arrival_time", Some(self.arrival_time)),
+            ("error", Some(self.error)),
+            ("distance_m", self.distance_m),
+            ("redshift", self.redshift),
+            ("error_low", self.error_low),
+            ("error_high", self.error_high),
+            ("weight", self.weight),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value.filter(|v| !v.is_finite()) {
+                return Err(TimingDataError::NonFinite { field, value });
+            }
+        }
+        if self.energy <= 0.0 {
+            return Err(TimingDataError::NonPositiveEnergy(self.energy));
+        }
+        for error in [Some(self.error), self.error_low, self.error_high].into_iter().flatten() {
+            if error <= 0.0 {
+                return Err(TimingDataError::NonPositiveError(error));
+            }
+        }
+        if let Some(weight) = self.weight.filter(|&w| w <= 0.0) {
+            return Err(TimingDataError::NonPositiveWeight(weight));
+        }
+        Ok(())
+    }
+}
+
+/// Location of a gamma-ray source shared by all photons tagged with its identifier
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SourceGeometry {
+    /// Distance in meters
+    Distance(f64),
+    /// Redshift, converted with the analyzer's cosmology
+    Redshift(f64),
+}
+
+/// Zero point of the arrival times handed to the analyzer
+///
+/// The analyzer stores every arrival time in seconds since the Unix epoch, converting
+/// incoming measurements from the reference in effect when they are added.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TimeReference {
+    /// Seconds since 1970-01-01T00:00:00 UTC
+    #[default]
+    UnixEpoch,
+    /// Seconds since a burst trigger at Unix time `t0`
+    SecondsSinceTrigger { t0: f64 },
+    /// Mission elapsed time counted from a mission epoch at Unix time `epoch`
+    MissionElapsedTime { epoch: f64 },
+}
+
+impl TimeReference {
+    /// Unix time of this reference's zero point
+    pub fn offset(self) -> f64 {
+        match self {
+            TimeReference::UnixEpoch => 0.0,
+            TimeReference::SecondsSinceTrigger { t0 } => t0,
+            TimeReference::MissionElapsedTime { epoch } => epoch,
+        }
+    }
+
+    /// Converts a time in this reference to seconds since the Unix epoch
+    pub fn to_unix(self, time: f64) -> f64 {
+        time + self.offset()
+    }
+
+    /// Converts seconds since the Unix epoch to a time in this reference
+    pub fn from_unix(self, time: f64) -> f64 {
+        time - self.offset()
+    }
+
+    /// Converts a time in this reference to the same instant in `target`
+    pub fn convert(self, time: f64, target: TimeReference) -> f64 {
+        target.from_unix(self.to_unix(time))
+    }
 }
 
-/// Main analyzer for gamma-ray timing data
-pub struct GammaRayAnalyzer {
-    measurements: Vec<TimingData>,
-    sensitivity_threshold: f64,
-    quantum_gravity_model: Option<QuantumGravityModel>,
-}
+/// Loss function minimized by the chi-squared test and the slope fits
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LossKind {
+    /// Ordinary inverse-variance weighted least squares
+    #[default]
+    LeastSquares,
+    /// Quadratic within `delta` standard errors of the fit and linear beyond, so
+    /// outliers pull on the result with bounded influence
+    Huber { delta: f64 },
+}
+
+impl LossKind {
+    /// Iteratively reweighted least-squares factor for a residual of `residual` sigma
+    fn weight(self, residual: f64) -> f64 {
+        match self {
+            LossKind::LeastSquares => 1.0,
+            LossKind::Huber { delta } if residual.abs() > delta => delta / residual.abs(),
+            LossKind::Huber { .. } => 1.0,
+        }
+    }
+}
+
+/// Reasons an analysis cannot be carried out on the loaded data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnalysisError {
+    /// The analyzer holds no measurements
+    NoData,
+    /// The measurement at `index` has an error giving a non-finite weight
+    ZeroError { index: usize },
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalysisError::NoData => write!(f, "no measurements to analyze"),
+            AnalysisError::ZeroError { index } => {
+                write!(f, "measurement {} has a zero or non-finite error", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+/// Reasons a `TimingData` measurement is rejected as non-physical
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimingDataError {
+    /// The photon energy is zero or negative
+    NonPositiveEnergy(f64),
+    /// The measurement error is zero or negative
+    NonPositiveError(f64),
+    /// The photon weight is zero or negative
+    NonPositiveWeight(f64),
+    /// A field is NaN or infinite
+    NonFinite { field: &'static str, value: f64 },
+}
+
+impl fmt::Display for TimingDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimingDataError::NonPositiveEnergy(energy) => {
+                write!(f, "energy must be positive, got {}", energy)
+            }
+            TimingDataError::NonPositiveError(error) => {
+                write!(f, "measurement error must be positive, got {}", error)
+            }
+            TimingDataError::NonPositiveWeight(weight) => {
+                write!(f, "weight must be positive, got {}", weight)
+            }
+            TimingDataError::NonFinite { field, value } => {
+                write!(f, "{} must be finite, got {}", field, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimingDataError {}
+
+/// Reasons two analyzers cannot be combined with `GammaRayAnalyzer::merge`
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeError {
+    /// The analyzers use different cosmologies
+    CosmologyMismatch,
+    /// The analyzers simulate different quantum gravity models
+    ModelMismatch,
+    /// Both analyzers register the source `id` with different geometries
+    SourceConflict { id: String },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::CosmologyMismatch => write!(f, "analyzers use different cosmologies"),
+            MergeError::ModelMismatch => {
+                write!(f, "analyzers use different quantum gravity models")
+            }
+            MergeError::SourceConflict { id } => {
+                write!(f, "source {:?} is registered with different geometries", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Receives the completed fraction of a long-running analysis, in [0, 1]
+pub type ProgressCallback = Box<dyn Fn(f64) + Send + Sync>;
+
+/// Settings that determine how a `GammaRayAnalyzer` evaluates its measurements
+///
+/// Recorded with `GammaRayAnalyzer::config` and restored with
+/// `GammaRayAnalyzer::from_config`. Measurements, registered sources and callbacks
+/// are not part of the configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisConfig {
+    pub sensitivity_threshold: f64,
+    pub anomaly_sigma: f64,
+    pub significance_level: f64,
+    #[serde(default)]
+    pub systematic_error: f64,
+    #[serde(default)]
+    pub reference_time: Option<f64>,
+    #[serde(default)]
+    pub time_reference: TimeReference,
+    #[serde(default)]
+    pub loss: LossKind,
+    #[serde(default)]
+    pub effective_area: Option<Vec<(f64, f64)>>,
+    pub quantum_gravity_model: Option<QuantumGravityModel>,
+    pub cosmology: Option<Cosmology>,
+}
+
+/// Main analyzer for gamma-ray timing data
+pub struct GammaRayAnalyzer {
+    measurements: Vec<TimingData>,
+    sensitivity_threshold: f64,
+    anomaly_sigma: f64,
+    significance_level: f64,
+    systematic_error: f64,
+    reference_time: Option<f64>,
+    time_reference: TimeReference,
+    loss: LossKind,
+    // Instrument effective area as ascending (energy, area) pairs
+    effective_area: Option<Vec<(f64, f64)>>,
+    quantum_gravity_model: Option<QuantumGravityModel>,
+    cosmology: Option<Cosmology>,
+    sources: HashMap<String, SourceGeometry>,
+    streaming: StreamingStatistics,
+    progress_callback: Option<ProgressCallback>,
+    // Memoized expected arrivals; reset whenever measurements or the model change
+    expected_cache: OnceLock<Vec<(f64, f64)>>,
+}
+
+impl GammaRayAnalyzer {
+    /// Creates a new analyzer with default settings
+    pub fn new() -> Self {
+        Self {
+            measurements: Vec::new(),
+            sensitivity_threshold: 1e-12,
+            anomaly_sigma: 3.0,
+            significance_level: 0.05,
+            systematic_error: 0.0,
+            reference_time: None,
+            time_reference: TimeReference::UnixEpoch,
+            loss: LossKind::LeastSquares,
+            effective_area: None,
+            quantum_gravity_model: None,
+            cosmology: None,
+            sources: HashMap::new(),
+            streaming: StreamingStatistics::default(),
+            progress_callback: None,
+            expected_cache: OnceLock::new(),
+        }
+    }
+
+    /// Creates an empty analyzer with the given configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if the anomaly sigma, significance level, systematic error, loss or
+    /// effective-area curve is invalid, as the corresponding setters do.
+    pub fn from_config(config: AnalysisConfig) -> Self {
+        let mut analyzer = Self::new();
+        analyzer.set_sensitivity_threshold(config.sensitivity_threshold);
+        analyzer.set_anomaly_sigma(config.anomaly_sigma);
+        analyzer.set_significance_level(config.significance_level);
+        analyzer.set_systematic_error(config.systematic_error);
+        // The recorded reference time is Unix, so restore it before the time reference
+        if let Some(t0) = config.reference_time {
+            analyzer.set_reference_time(t0);
+        }
+        analyzer.time_reference = config.time_reference;
+        analyzer.set_loss(config.loss);
+        if let Some(curve) = config.effective_area {
+            analyzer.load_effective_area(curve);
+        }
+        analyzer.quantum_gravity_model = config.quantum_gravity_model;
+        analyzer.cosmology = config.cosmology;
+        analyzer
+    }
+
+    /// Current configuration, suitable for recording alongside results
+    pub fn config(&self) -> AnalysisConfig {
+        AnalysisConfig {
+            sensitivity_threshold: self.sensitivity_threshold,
+            anomaly_sigma: self.anomaly_sigma,
+            significance_level: self.significance_level,
+            systematic_error: self.systematic_error,
+            reference_time: self.reference_time,
+            time_reference: self.time_reference,
+            loss: self.loss,
+            effective_area: self.effective_area.clone(),
+            quantum_gravity_model: self.quantum_gravity_model.clone(),
+            cosmology: self.cosmology.clone(),
+        }
+    }
+
+    /// Adds a timing measurement to the dataset
+    pub fn add_measurement(&mut self, energy: f64, arrival_time: f64, error: f64) {
+        self.push_measurement(TimingData::new(energy, arrival_time, error));
+    }
+
+    /// Stores a measurement, converting its arrival time from the current time reference
+    fn push_measurement(&mut self, measurement: TimingData) {
+        self.invalidate_cache();
+        self.measurements.push(self.normalize_time(measurement));
+    }
+
+    /// Re-expresses a measurement's arrival time in seconds since the Unix epoch
+    fn normalize_time(&self, mut measurement: TimingData) -> TimingData {
+        measurement.arrival_time = self.time_reference.to_unix(measurement.arrival_time);
+        measurement
+    }
+
+    /// Adds a measurement after checking it with `TimingData::validate`
+    ///
+    /// Rejected measurements leave the analyzer unchanged.
+    pub fn try_add_measurement(
+        &mut self,
+        energy: f64,
+        arrival_time: f64,
+        error: f64,
+    ) -> Result<(), TimingDataError> {
+        let measurement = TimingData::new(energy, arrival_time, error);
+        measurement.validate()?;
+        self.push_measurement(measurement);
+        Ok(())
+    }
+
+    /// Adds a batch of timing measurements, reserving capacity up front
+    pub fn add_measurements(&mut self, data: &[TimingData]) {
+        self.invalidate_cache();
+        self.measurements.reserve(data.len());
+        let reference = self.time_reference;
+        self.measurements.extend(data.iter().map(|m| TimingData {
+            arrival_time: reference.to_unix(m.arrival_time),
+            ..m.clone()
+        }));
+    }
+
+    /// Adds a timing measurement from a source at a known distance (meters)
+    pub fn add_measurement_with_distance(
+        &mut self,
+        energy: f64,
+        arrival_time: f64,
+        error: f64,
+        distance_m: f64,
+    ) {
+        let measurement = TimingData::new(energy, arrival_time, error).with_distance(distance_m);
+        self.push_measurement(measurement);
+    }
+
+    /// Adds a timing measurement from a source at a known redshift
+    pub fn add_measurement_with_redshift(
+        &mut self,
+        energy: f64,
+        arrival_time: f64,
+        error: f64,
+        redshift: f64,
+    ) {
+        self.push_measurement(TimingData::new(energy, arrival_time, error).with_redshift(redshift));
+    }
+
+    /// Appends another analyzer's measurements, sources and streaming sums
+    ///
+    /// Both analyzers must share the same cosmology and quantum gravity model, and any
+    /// source registered in both must have the same geometry. The remaining settings
+    /// of `self` (thresholds, significance level, systematic error, callback) are
+    /// kept. On error `self` is left unchanged.
+    pub fn merge(&mut self, other: GammaRayAnalyzer) -> Result<(), MergeError> {
+        if self.cosmology != other.cosmology {
+            return Err(MergeError::CosmologyMismatch);
+        }
+        if self.quantum_gravity_model != other.quantum_gravity_model {
+            return Err(MergeError::ModelMismatch);
+        }
+        for (id, geometry) in &other.sources {
+            if self.sources.get(id).is_some_and(|existing| existing != geometry) {
+                return Err(MergeError::SourceConflict { id: id.clone() });
+            }
+        }
+
+        self.invalidate_cache();
+        self.measurements.extend(other.measurements);
+        self.sources.extend(other.sources);
+        let stats = &mut self.streaming;
+        stats.count += other.streaming.count;
+        stats.chi_squared.merge(other.streaming.chi_squared);
+        stats.weighted_deviation.merge(other.streaming.weighted_deviation);
+        stats.total_weight.merge(other.streaming.total_weight);
+        stats.anomalies += other.streaming.anomalies;
+        Ok(())
+    }
+
+    /// Removes and returns the measurement at `index`, or `None` if out of range
+    ///
+    /// Indices are positions in insertion order, as returned by `measurements()`;
+    /// later measurements shift down by one.
+    pub fn remove_measurement(&mut self, index: usize) -> Option<TimingData> {
+        if index >= self.measurements.len() {
+            return None;
+        }
+        self.invalidate_cache();
+        Some(self.measurements.remove(index))
+    }
+
+    /// Keeps only measurements arriving within `[start, end]` and returns how many remain
+    ///
+    /// `start` and `end` are in the current time reference, like incoming arrival
+    /// times. Both ends of the window are inclusive; the order of the kept measurements
+    /// is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `start < end`.
+    pub fn filter_time_window(&mut self, start: f64, end: f64) -> usize {
+        assert!(start < end, "time window start must be before its end, got [{}, {}]", start, end);
+        let start = self.time_reference.to_unix(start);
+        let end = self.time_reference.to_unix(end);
+
+        let before = self.measurements.len();
+        self.measurements.retain(|m| m.arrival_time >= start && m.arrival_time <= end);
+        if self.measurements.len() != before {
+            self.invalidate_cache();
+        }
+        self.measurements.len()
+    }
+
+    /// Keeps only photons with energies within `[min_gev, max_gev]` and returns how many remain
+    ///
+    /// Both ends of the band are inclusive; the order of the kept measurements is
+    /// unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either bound is negative or `min_gev` is not below `max_gev`.
+    pub fn filter_energy_range(&mut self, min_gev: f64, max_gev: f64) -> usize {
+        assert!(
+            min_gev >= 0.0 && max_gev >= 0.0,
+            "energy bounds must be non-negative, got [{}, {}]",
+            min_gev,
+            max_gev
+        );
+        assert!(
+            min_gev < max_gev,
+            "minimum energy must be below the maximum, got [{}, {}]",
+            min_gev,
+            max_gev
+        );
+
+        let before = self.measurements.len();
+        self.measurements.retain(|m| m.energy >= min_gev && m.energy <= max_gev);
+        if self.measurements.len() != before {
+            self.invalidate_cache();
+        }
+        self.measurements.len()
+    }
+
+    /// Removes all measurements, keeping the analyzer's configuration
+    pub fn clear(&mut self) {
+        self.invalidate_cache();
+        self.measurements.clear();
+    }
+
+    /// Returns the loaded measurements in insertion order
+    ///
+    /// ```
+    /// use lightspeedvalidator::GammaRayAnalyzer;
+    ///
+    /// let mut analyzer = GammaRayAnalyzer::new();
+    /// analyzer.add_measurement(100.0, 1234567890.123, 0.001);
+    /// analyzer.add_measurement(200.0, 1234567891.456, 0.002);
+    /// analyzer.add_measurement(500.0, 1234567893.789, 0.003);
+    ///
+    /// assert_eq!(analyzer.len(), 3);
+    /// let energies: Vec<f64> = analyzer.measurements().iter().map(|m| m.energy).collect();
+    /// assert_eq!(energies, vec![100.0, 200.0, 500.0]);
+    /// ```
+    pub fn measurements(&self) -> &[TimingData] {
+        &self.measurements
+    }
+
+    /// Number of loaded measurements
+    pub fn len(&self) -> usize {
+        self.measurements.len()
+    }
+
+    /// Returns true if no measurements have been loaded
+    pub fn is_empty(&self) -> bool {
+        self.measurements.is_empty()
+    }
+
+    /// Returns the sensitivity threshold for detecting deviations
+    pub fn sensitivity_threshold(&self) -> f64 {
+        self.sensitivity_threshold
+    }
+
+    /// Inverse-error² weighted mean of the arrival times
+    ///
+    /// Measurements whose error is not positive and finite carry no weight. Returns
+    /// `None` when no measurement has usable weight.
+    pub fn weighted_mean_arrival(&self) -> Option<f64> {
+        let terms: Vec<(f64, f64)> = self
+            .measurements
+            .iter()
+            .filter_map(|m| self.inverse_variance_weight(m).map(|w| (w * m.arrival_time, w)))
+            .collect();
+        let total_weight = stable_sum(terms.iter().map(|t| t.1));
+        (total_weight > 0.0).then(|| stable_sum(terms.iter().map(|t| t.0)) / total_weight)
+    }
+
+    /// Inverse-error² weighted variance of the arrival times about their weighted mean
+    ///
+    /// Computed as `Σ w (t - μ)² / Σ w` over the same measurements as
+    /// `weighted_mean_arrival`, returning `None` in the same cases.
+    pub fn weighted_arrival_variance(&self) -> Option<f64> {
+        let mean = self.weighted_mean_arrival()?;
+        let terms: Vec<(f64, f64)> = self
+            .measurements
+            .iter()
+            .filter_map(|m| {
+                let deviation = m.arrival_time - mean;
+                self.inverse_variance_weight(m).map(|w| (w * deviation * deviation, w))
+            })
+            .collect();
+        Some(stable_sum(terms.iter().map(|t| t.0)) / stable_sum(terms.iter().map(|t| t.1)))
+    }
+
+    /// Sets the sensitivity threshold for detecting deviations
+    pub fn set_sensitivity_threshold(&mut self, threshold: f64) {
+        self.invalidate_cache();
+        self.sensitivity_threshold = threshold;
+    }
+
+    /// Sets the significance (in sigma) above which a measurement is flagged as anomalous
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is not a positive, finite number.
+    pub fn set_anomaly_sigma(&mut self, sigma: f64) {
+        assert!(
+            sigma.is_finite() && sigma > 0.0,
+            "anomaly sigma must be positive and finite, got {}",
+            sigma
+        );
+        self.anomaly_sigma = sigma;
+    }
+
+    /// Sets the significance level α below which a p-value rejects constant c
+    ///
+    /// The default of 0.05 corresponds to 95% confidence; a result is valid when its
+    /// p-value exceeds α.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not strictly between 0 and 1.
+    pub fn set_significance_level(&mut self, alpha: f64) {
+        assert!(
+            alpha > 0.0 && alpha < 1.0,
+            "significance level must be in (0, 1), got {}",
+            alpha
+        );
+        self.significance_level = alpha;
+    }
+
+    /// Sets a systematic timing uncertainty added in quadrature to every error
+    ///
+    /// Each measurement's error becomes `sqrt(error² + sigma_sys²)` wherever it is used
+    /// as a weight: the constancy test, anomaly detection, fits and weighted means.
+    /// The default of zero leaves the statistical errors unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma_sys` is negative or not finite.
+    pub fn set_systematic_error(&mut self, sigma_sys: f64) {
+        assert!(
+            sigma_sys.is_finite() && sigma_sys >= 0.0,
+            "systematic error must be non-negative and finite, got {}",
+            sigma_sys
+        );
+        self.systematic_error = sigma_sys;
+    }
+
+    /// Systematic timing uncertainty in seconds added to every error
+    pub fn systematic_error(&self) -> f64 {
+        self.systematic_error
+    }
+
+    /// Sets the time reference of arrival times passed in from now on
+    ///
+    /// Measurements already stored keep their converted times, so sources recorded
+    /// against different references can be added one after another. Times passed to
+    /// `set_reference_time` and `filter_time_window`, and `reference_time` itself, use
+    /// the current reference; everything else the analyzer reports is in seconds since
+    /// the Unix epoch.
+    ///
+    /// Times are stored as Unix `f64` seconds, whose spacing near present-day epochs
+    /// (about 1.7e9 s) is about 2.4e-7 s, so trigger-relative times are rounded to a
+    /// fraction of a microsecond.
+    pub fn set_time_reference(&mut self, reference: TimeReference) {
+        self.time_reference = reference;
+    }
+
+    /// Time reference applied to incoming arrival times
+    pub fn time_reference(&self) -> TimeReference {
+        self.time_reference
+    }
+
+    /// Sets the loss minimized by the constancy test and the slope fits
+    ///
+    /// Under `LossKind::Huber` the fits are iteratively reweighted: a measurement more
+    /// than `delta` standard errors from the current fit has its weight scaled by
+    /// `delta / |residual|`, and the chi-squared sums the reweighted terms. Those
+    /// weights depend on the data, so the statistic no longer follows a chi-squared
+    /// distribution exactly and the reported p-value is approximate. The streaming
+    /// statistics of `accumulate` always use least squares.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a Huber `delta` is not positive and finite.
+    pub fn set_loss(&mut self, loss: LossKind) {
+        if let LossKind::Huber { delta } = loss {
+            assert!(
+                delta.is_finite() && delta > 0.0,
+                "Huber delta must be positive and finite, got {}",
+                delta
+            );
+        }
+        self.loss = loss;
+    }
+
+    /// Loss minimized by the constancy test and the slope fits
+    pub fn loss(&self) -> LossKind {
+        self.loss
+    }
+
+    /// Sets the common zero of arrival times for photons without source geometry
+    ///
+    /// The constant-c model then expects every such photon at `t0` plus its model
+    /// delay, so residuals are `(arrival - t0) - delay`. `t0` is in the current time
+    /// reference (see `set_time_reference`), like incoming arrival times. Without an
+    /// explicit reference the earliest arrival among the stored photons without
+    /// geometry is used. Photons with a known distance are still measured from
+    /// emission at t = 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t0` is not finite.
+    pub fn set_reference_time(&mut self, t0: f64) {
+        assert!(t0.is_finite(), "reference time must be finite, got {}", t0);
+        self.invalidate_cache();
+        self.reference_time = Some(self.time_reference.to_unix(t0));
+    }
+
+    /// Reference arrival time for photons without geometry, in the current time reference
+    ///
+    /// The value passed to `set_reference_time`, else the earliest arrival among the
+    /// stored photons without geometry, or the Unix epoch when there are none.
+    pub fn reference_time(&self) -> f64 {
+        self.time_reference.from_unix(self.unix_reference_time())
+    }
+
+    /// `reference_time` in seconds since the Unix epoch
+    fn unix_reference_time(&self) -> f64 {
+        self.reference_time.unwrap_or_else(|| {
+            self.measurements
+                .iter()
+                .filter(|m| self.travel_time(m).is_none())
+                .map(|m| m.arrival_time)
+                .reduce(f64::min)
+                .unwrap_or(0.0)
+        })
+    }
+
+    /// Significance level used to judge `LightSpeedTestResult::is_valid`
+    pub fn significance_level(&self) -> f64 {
+        self.significance_level
+    }
+
+    /// Sets up quantum gravity model simulation
+    pub fn enable_quantum_gravity_simulation(&mut self, model: QuantumGravityModel) {
+        self.invalidate_cache();
+        self.quantum_gravity_model = Some(model);
+    }
+
+    /// Installs a callback that the constancy test calls with its completed fraction
+    ///
+    /// The callback is invoked about every 1% of measurements, with non-decreasing
+    /// values ending at exactly 1.0. It must be `Send + Sync` so the analyzer can still
+    /// be shared across threads.
+    pub fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress_callback = Some(callback);
+    }
+
+    /// Removes the progress callback
+    pub fn clear_progress_callback(&mut self) {
+        self.progress_callback = None;
+    }
+
+    /// Attaches a cosmology used to turn measurement redshifts into distances
+    pub fn set_cosmology(&mut self, cosmology: Cosmology) {
+        self.invalidate_cache();
+        self.cosmology = Some(cosmology);
+    }
+
+    /// Loads the instrument effective area as `(energy in GeV, area)` pairs
+    ///
+    /// Each photon's inverse-variance weight is multiplied by `A(E) / A_max`, folding the
+    /// inverse relative area into its variance, so photons from energies where the
+    /// instrument is less sensitive count for less. `A(E)` is interpolated linearly in
+    /// log-energy and log-area and held at the end values outside the curve; `A_max` is
+    /// the largest area in the curve, so the units of area do not matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the curve is empty, its energies are not strictly increasing, or any
+    /// energy or area is not positive and finite.
+    pub fn load_effective_area(&mut self, curve: Vec<(f64, f64)>) {
+        assert!(!curve.is_empty(), "effective-area curve must not be empty");
+        for &(energy, area) in &curve {
+            assert!(
+                energy.is_finite() && energy > 0.0 && area.is_finite() && area > 0.0,
+                "effective-area energies and areas must be positive and finite, got ({}, {})",
+                energy,
+                area
+            );
+        }
+        if let Some(pair) = curve.windows(2).find(|pair| pair[1].0 <= pair[0].0) {
+            panic!(
+                "effective-area energies must be strictly increasing, got {} followed by {}",
+                pair[0].0, pair[1].0
+            );
+        }
+        self.effective_area = Some(curve);
+    }
+
+    /// Removes the effective-area curve, restoring unit area weights
+    pub fn clear_effective_area(&mut self) {
+        self.effective_area = None;
+    }
+
+    /// Interpolated effective area at `energy`, or `None` without a curve
+    pub fn effective_area_at(&self, energy: f64) -> Option<f64> {
+        let curve = self.effective_area.as_ref()?;
+        let upper = curve.partition_point(|&(e, _)| e < energy);
+        if upper == 0 {
+            return Some(curve[0].1);
+        }
+        if upper == curve.len() {
+            return Some(curve[upper - 1].1);
+        }
+        let (e0, a0) = curve[upper - 1];
+        let (e1, a1) = curve[upper];
+        let fraction = (energy / e0).ln() / (e1 / e0).ln();
+        Some(a0 * (a1 / a0).powf(fraction))
+    }
+
+    /// Photon weight times the relative effective area at the photon's energy
+    fn photon_weight(&self, measurement: &TimingData) -> f64 {
+        let relative_area = match &self.effective_area {
+            Some(curve) => {
+                let peak = curve.iter().map(|&(_, area)| area).fold(0.0, f64::max);
+                self.effective_area_at(measurement.energy).unwrap_or(peak) / peak
+            }
+            None => 1.0,
+        };
+        measurement.weight_factor() * relative_area
+    }
+
+    /// Registers the geometry of a source so photons tagged with `source_id` use it
+    ///
+    /// Registering an existing identifier replaces its geometry.
+    pub fn add_source(&mut self, source_id: impl Into<String>, geometry: SourceGeometry) {
+        self.invalidate_cache();
+        self.sources.insert(source_id.into(), geometry);
+    }
+
+    /// Returns the registered source geometries keyed by source identifier
+    pub fn sources(&self) -> &HashMap<String, SourceGeometry> {
+        &self.sources
+    }
+
+    /// Light-travel time for a measurement, from its source or its own distance or redshift
+    ///
+    /// A photon tagged with a registered source uses that source's geometry; otherwise
+    /// its own distance takes precedence over its redshift. Redshifts are only used
+    /// when a cosmology is attached.
+    fn travel_time(&self, measurement: &TimingData) -> Option<f64> {
+        self.memoized_travel_time(measurement, None)
+    }
+
+    /// `travel_time`, looking redshift distances up in `distances` when they are given
+    fn memoized_travel_time(
+        &self,
+        measurement: &TimingData,
+        distances: Option<&HashMap<u64, f64>>,
+    ) -> Option<f64> {
+        let redshift_travel_time = |z: f64| {
+            let cosmology = self.cosmology.as_ref()?;
+            let distance = distances
+                .and_then(|distances| distances.get(&z.to_bits()).copied())
+                .unwrap_or_else(|| cosmology.comoving_distance(z));
+            Some(distance / SPEED_OF_LIGHT)
+        };
+
+        let source = measurement.source_id.as_ref().and_then(|id| self.sources.get(id));
+        match source {
+            Some(SourceGeometry::Distance(distance_m)) => Some(distance_m / SPEED_OF_LIGHT),
+            Some(SourceGeometry::Redshift(z)) => redshift_travel_time(*z),
+            None => measurement
+                .light_travel_time()
+                .or_else(|| redshift_travel_time(measurement.redshift?)),
+        }
+    }
+
+    /// Redshift that determines a measurement's travel time, if any
+    ///
+    /// Follows the precedence of `travel_time`: a registered source, then the photon's
+    /// own distance (which rules out a redshift), then its own redshift.
+    fn redshift(&self, measurement: &TimingData) -> Option<f64> {
+        let source = measurement.source_id.as_ref().and_then(|id| self.sources.get(id));
+        match source {
+            Some(SourceGeometry::Redshift(z)) => Some(*z),
+            Some(SourceGeometry::Distance(_)) => None,
+            None if measurement.distance_m.is_some() => None,
+            None => measurement.redshift,
+        }
+    }
+
+    /// Performs analysis to test light speed constancy hypothesis
+    ///
+    /// Empty data yields a trivially valid result and zero errors propagate as
+    /// inf/NaN; use `try_test_light_speed_constancy` to reject such input instead.
+    pub fn test_light_speed_constancy(&self) -> LightSpeedTestResult {
+        self.try_test_light_speed_constancy().unwrap_or_else(|error| match error {
+            AnalysisError::NoData => LightSpeedTestResult::no_data(),
+            AnalysisError::ZeroError { .. } => self.evaluate_constancy(),
+        })
+    }
+
+    /// Tests light speed constancy, rejecting degenerate input
+    ///
+    /// Returns `AnalysisError::NoData` when there are no measurements and
+    /// `AnalysisError::ZeroError` for the first measurement whose weight `1 / error²`
+    /// is not finite.
+    pub fn try_test_light_speed_constancy(&self) -> Result<LightSpeedTestResult, AnalysisError> {
+        if self.measurements.is_empty() {
+            return Err(AnalysisError::NoData);
+        }
+        if let Some(index) = self.measurements.iter().position(|m| {
+            [Some(m.error), m.error_low, m.error_high]
+                .into_iter()
+                .flatten()
+                .map(|error| self.total_error(error))
+                .any(|error| !(1.0 / (error * error)).is_finite())
+        }) {
+            return Err(AnalysisError::ZeroError { index });
+        }
+
+        Ok(self.evaluate_constancy())
+    }
+
+    /// Folds one measurement into the streaming statistics without storing it
+    ///
+    /// The measurement's expected arrival uses the analyzer's current model, cosmology,
+    /// sources and reference time, but always least squares: Huber reweighting needs the
+    /// fit to every measurement, so `set_loss` does not affect the streaming statistics.
+    /// Call `finalize_streaming` once every measurement has been accumulated; the stored
+    /// measurements are not changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the measurement has no source geometry and no reference time was set
+    /// with `set_reference_time`: the default, the earliest such arrival, is not known
+    /// until the whole stream has been read.
+    pub fn accumulate(&mut self, data: &TimingData) {
+        let data = &self.normalize_time(data.clone());
+        assert!(
+            self.reference_time.is_some() || self.travel_time(data).is_some(),
+            "streaming a photon without source geometry needs set_reference_time first"
+        );
+        let reference_time = self.reference_time.unwrap_or(0.0);
+        let (_, expected_time) = self.expected_arrival(data, reference_time, None);
+        let deviation = data.arrival_time - expected_time;
+        let error = self.residual_error(data, deviation);
+        let weight = self.photon_weight(data) / (error * error);
+
+        let stats = &mut self.streaming;
+        stats.count += 1;
+        stats.chi_squared.add(weight * deviation * deviation);
+        stats.weighted_deviation.add(weight * deviation);
+        stats.total_weight.add(weight);
+        if deviation.abs() / error > self.anomaly_sigma {
+            stats.anomalies += 1;
+        }
+    }
+
+    /// Constancy test over everything passed to `accumulate`
+    ///
+    /// Gives exactly the result `test_light_speed_constancy` would for the same
+    /// measurements held in memory, in the same order, with the same reference time and
+    /// `LossKind::LeastSquares`. Under a Huber loss the in-memory test downweights
+    /// outliers and the two results differ.
+    pub fn finalize_streaming(&self) -> LightSpeedTestResult {
+        let stats = &self.streaming;
+        if stats.count == 0 {
+            return LightSpeedTestResult::no_data();
+        }
+        LightSpeedTestResult::from_sums(
+            self.significance_level,
+            stats.count,
+            stats.chi_squared.total(),
+            stats.weighted_deviation.total(),
+            stats.total_weight.total(),
+            stats.anomalies,
+        )
+    }
+
+    /// Discards the statistics gathered by `accumulate`
+    pub fn reset_streaming(&mut self) {
+        self.streaming = StreamingStatistics::default();
+    }
+
+    /// Chi-squared test of the measurements against the constant-c expectation
+    fn evaluate_constancy(&self) -> LightSpeedTestResult {
+        // Calculate expected arrival times assuming c = 299792458 m/s
+        let expected_times = self.calculate_expected_arrivals();
+
+        // Calculate chi-squared statistic
+        let mut terms = self.chi_squared_terms(expected_times);
+        self.reweight_terms(&mut terms);
+
+        // Summed serially in measurement order so the result does not depend on threading,
+        // with compensation so precision holds over millions of terms
+        let total = terms.len();
+        let report_every = (total / 100).max(1);
+        let mut chi_squared = CompensatedSum::default();
+        let mut total_weighted_deviation = CompensatedSum::default();
+        let mut total_weight = CompensatedSum::default();
+        for (index, term) in terms.iter().enumerate() {
+            chi_squared.add(term.0);
+            total_weighted_deviation.add(term.1);
+            total_weight.add(term.2);
+            
+            if let Some(ref callback) = self.progress_callback {
+                let done = index + 1;
+                if done % report_every == 0 || done == total {
+                    callback(done as f64 / total as f64);
+                }
+            }
+        }
+
+        LightSpeedTestResult::from_sums(
+            self.significance_level,
+            self.measurements.len(),
+            chi_squared.total(),
+            total_weighted_deviation.total(),
+            total_weight.total(),
+            self.detect_anomalies().len(),
+        )
+    }
+
+    /// Per-measurement `(weight · deviation², weight · deviation, weight)` terms
+    fn chi_squared_terms(&self, expected_times: &[(f64, f64)]) -> Vec<(f64, f64, f64)> {
+        let term = |(measurement, expected): (&TimingData, &(f64, f64))| {
+            let deviation = measurement.arrival_time - expected.1;
+            let error = self.residual_error(measurement, deviation);
+            let weight = self.photon_weight(measurement) / (error * error);
+            (weight * deviation * deviation, weight * deviation, weight)
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            self.measurements.par_iter().zip(expected_times.par_iter()).map(term).collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.measurements.iter().zip(expected_times.iter()).map(term).collect()
+        }
+    }
+
+    /// Scales chi-squared terms by the robust weights of the configured loss
+    ///
+    /// The weights are iterated together with the weighted mean deviation, measuring
+    /// each residual from that mean in units of its error. Least squares leaves the
+    /// terms untouched.
+    fn reweight_terms(&self, terms: &mut [(f64, f64, f64)]) {
+        if self.loss == LossKind::LeastSquares {
+            return;
+        }
+        let deviation = |term: &(f64, f64, f64)| term.1 / term.2;
+        let weighted_mean = |factors: &[f64]| {
+            let mut weighted_deviation = CompensatedSum::default();
+            let mut total_weight = CompensatedSum::default();
+            for (term, factor) in terms.iter().zip(factors).filter(|(t, _)| t.2 > 0.0) {
+                weighted_deviation.add(factor * term.1);
+                total_weight.add(factor * term.2);
+            }
+            (weighted_deviation.total() / total_weight.total(), total_weight.total())
+        };
+
+        let mut factors = vec![1.0; terms.len()];
+        let (mut mean, _) = weighted_mean(&factors);
+        for _ in 0..IRLS_MAX_ITERATIONS {
+            for (factor, term) in factors.iter_mut().zip(terms.iter()) {
+                if term.2 > 0.0 && term.2.is_finite() {
+                    *factor = self.loss.weight((deviation(term) - mean) * term.2.sqrt());
+                }
+            }
+            let (next, total_weight) = weighted_mean(&factors);
+            let converged = (next - mean).abs() <= IRLS_TOLERANCE / total_weight.sqrt();
+            mean = next;
+            if converged {
+                break;
+            }
+        }
+        for (term, factor) in terms.iter_mut().zip(factors) {
+            *term = (factor * term.0, factor * term.1, factor * term.2);
+        }
+    }
+
+    /// Calculates expected arrival times based on light speed assumption
+    ///
+    /// When a measurement carries its source distance (or a redshift and the analyzer
+    /// has a cosmology), the expected arrival is the geometric light-travel time
+    /// `distance / c` (measured from emission at t = 0) plus any quantum gravity delay.
+    /// Without a distance, the expectation is the reference time (see
+    /// `set_reference_time`) plus the model delay. For a source placed by redshift the
+    /// delay is the Jacob–Piran integral of `Cosmology::liv_time_delay`.
+    ///
+    /// The result is computed once and cached until the measurements, threshold or
+    /// model change, so repeated analyses share the same slice.
+    pub fn calculate_expected_arrivals(&self) -> &[(f64, f64)] {
+        self.expected_cache.get_or_init(|| self.compute_expected_arrivals())
+    }
+
+    /// Drops the memoized expected arrivals so the next request recomputes them
+    fn invalidate_cache(&mut self) {
+        self.expected_cache = OnceLock::new();
+    }
+
+    /// Computes expected arrivals for every measurement, bypassing the cache
+    fn compute_expected_arrivals(&self) -> Vec<(f64, f64)> {
+        let reference_time = self.unix_reference_time();
+        let distances = self.redshift_distances();
+        let distances = Some(&distances);
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            self.measurements
+                .par_iter()
+                .map(|measurement| self.expected_arrival(measurement, reference_time, distances))
+                .collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.measurements
+                .iter()
+                .map(|measurement| self.expected_arrival(measurement, reference_time, distances))
+                .collect()
+        }
+    }
+
+    /// Comoving distance for each distinct redshift in use, keyed by its bit pattern
+    ///
+    /// Photons sharing a source share a redshift, so each distance integral is
+    /// evaluated once rather than once per photon, with no change to the result.
+    fn redshift_distances(&self) -> HashMap<u64, f64> {
+        let mut distances = HashMap::new();
+        if let Some(cosmology) = &self.cosmology {
+            for z in self.measurements.iter().filter_map(|m| self.redshift(m)) {
+                distances
+                    .entry(z.to_bits())
+                    .or_insert_with(|| cosmology.comoving_distance(z));
+            }
+        }
+        distances
+    }
+
+    /// Expected `(energy, arrival_time)` for a single measurement
+    ///
+    /// Redshift distances come from `distances` when given, else from direct integration.
+    fn expected_arrival(
+        &self,
+        measurement: &TimingData,
+        reference_time: f64,
+        distances: Option<&HashMap<u64, f64>>,
+    ) -> (f64, f64) {
+        // Time delay due to energy-dependent propagation (if quantum gravity model is active)
+        let travel_time = self.memoized_travel_time(measurement, distances);
+        let redshift = self.redshift(measurement);
+        let time_delay = match (&self.quantum_gravity_model, &self.cosmology, redshift) {
+            // Cosmological sources integrate the delay over the expansion history
+            (Some(model), Some(cosmology), Some(z)) => cosmology.liv_time_delay(
+                z,
+                measurement.energy,
+                model.energy_scale,
+                model.dispersion_order,
+            ),
+            (Some(model), _, _) => model.time_delay(measurement.energy, travel_time),
+            (None, _, _) => 0.0,
+        };
+
+        let expected_time = match travel_time {
+            // Geometric time of flight plus energy-dependent delay
+            Some(travel_time) => travel_time + time_delay,
+            // Common reference arrival plus energy-dependent delay
+            None => reference_time + time_delay,
+        };
+        (measurement.energy, expected_time)
+    }
+
+    /// Fits the LIV delay slope of arrival time against energy
+    ///
+    /// Minimizes the weighted least-squares residual of `t = t0 + slope · E`; the slope
+    /// error comes from the curvature of the chi-squared surface. When the source
+    /// geometry is known the slope is also converted to a linear-order E_QG. With
+    /// fewer than two usable points or no energy spread the slope is NaN.
+    pub fn fit_liv_slope(&self) -> LivFitResult {
+        let measurements = self.weighting_measurements();
+        let fit = match self.linear_fit(measurements.iter()) {
+            Some(fit) => fit,
+            None => {
+                return LivFitResult {
+                    slope: f64::NAN,
+                    slope_error: f64::INFINITY,
+                    reduced_chi_squared: f64::NAN,
+                    energy_scale: None,
+                }
+            }
+        };
+
+        let degrees_of_freedom = fit.points.saturating_sub(2);
+        let reduced_chi_squared = if degrees_of_freedom > 0 {
+            fit.chi_squared / degrees_of_freedom as f64
+        } else {
+            f64::NAN
+        };
+        let energy_scale = self
+            .measurements
+            .iter()
+            .find_map(|m| self.travel_time(m))
+            .filter(|_| fit.slope != 0.0)
+            .map(|travel_time| travel_time / fit.slope.abs());
+
+        LivFitResult {
+            slope: fit.slope,
+            slope_error: fit.slope_variance.sqrt(),
+            reduced_chi_squared,
+            energy_scale,
+        }
+    }
+
+    /// Leave-one-out jackknife of the fitted LIV delay slope
+    ///
+    /// Refits the slope of `fit_liv_slope` with each measurement removed in turn. The
+    /// standard error is `sqrt((n-1)/n · Σ (slope_i - mean)²)`, and the most
+    /// influential measurement is the one whose removal moves the slope furthest from
+    /// the full fit. Returns `None` with fewer than three measurements or if any fit is
+    /// undefined.
+    pub fn jackknife_slope(&self) -> Option<JackknifeResult> {
+        let n = self.measurements.len();
+        if n < 3 {
+            return None;
+        }
+        let measurements = self.weighting_measurements();
+        let full_slope = self.linear_fit(measurements.iter())?.slope;
+
+        let slopes = (0..n)
+            .map(|left_out| {
+                let rest = measurements
+                    .iter()
+                    .enumerate()
+                    .filter(|&(index, _)| index != left_out)
+                    .map(|(_, m)| m);
+                self.linear_fit(rest).map(|fit| fit.slope)
+            })
+            .collect::<Option<Vec<f64>>>()?;
+
+        let mean_slope = slopes.iter().sum::<f64>() / n as f64;
+        let spread: f64 = slopes.iter().map(|s| (s - mean_slope).powi(2)).sum();
+        let standard_error = ((n - 1) as f64 / n as f64 * spread).sqrt();
+        let most_influential = slopes
+            .iter()
+            .map(|s| (s - full_slope).abs())
+            .enumerate()
+            .fold((0, f64::NEG_INFINITY), |best, (index, change)| {
+                if change > best.1 { (index, change) } else { best }
+            })
+            .0;
+
+        Some(JackknifeResult {
+            mean_slope,
+            standard_error,
+            most_influential,
+        })
+    }
+
+    /// Permutation p-value for an energy-dependent arrival delay
+    ///
+    /// The test statistic is the slope significance `|slope| / σ_slope` of the fit in
+    /// `fit_liv_slope`. Each of `iterations` trials shuffles the energies across the
+    /// photons, which keeps the arrival times and errors but breaks any dispersion
+    /// correlation, and refits. The observed pairing counts as one of the permutations,
+    /// so with `k` of the trials reaching the observed statistic the p-value is
+    /// `(k + 1) / (iterations + 1)` and never zero. Returns NaN when the slope cannot be
+    /// fitted. The same `seed` reproduces the same p-value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iterations` is zero.
+    pub fn monte_carlo_pvalue(&self, iterations: usize, seed: u64) -> f64 {
+        assert!(iterations > 0, "at least one Monte Carlo iteration is required");
+        let statistic = |data: &[TimingData]| {
+            self.linear_fit(data.iter()).map(|fit| fit.slope.abs() / fit.slope_variance.sqrt())
+        };
+        let measurements = self.weighting_measurements();
+        let observed = match statistic(&measurements) {
+            Some(observed) => observed,
+            None => return f64::NAN,
+        };
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut energies: Vec<f64> = measurements.iter().map(|m| m.energy).collect();
+        let mut shuffled = measurements.to_vec();
+        let mut exceeding = 0;
+        for _ in 0..iterations {
+            energies.shuffle(&mut rng);
+            for (measurement, &energy) in shuffled.iter_mut().zip(&energies) {
+                measurement.energy = energy;
+            }
+            if statistic(&shuffled).is_some_and(|trial| trial >= observed) {
+                exceeding += 1;
+            }
+        }
+        (exceeding + 1) as f64 / (iterations + 1) as f64
+    }
+
+    /// Weighted linear regression of arrival time on energy
+    ///
+    /// Fits `t = intercept + slope · E` with inverse-error² weights and reports both
+    /// parameters with their standard errors, their covariance and the weighted R².
+    /// Returns `None` with fewer than two usable points or fewer than two distinct
+    /// energies.
+    pub fn regress_time_on_energy(&self) -> Option<RegressionResult> {
+        let measurements = self.weighting_measurements();
+        self.linear_fit(measurements.iter()).map(|fit| RegressionResult {
+            slope: fit.slope,
+            slope_error: fit.slope_variance.sqrt(),
+            intercept: fit.intercept,
+            intercept_error: fit.intercept_variance.sqrt(),
+            slope_intercept_covariance: fit.covariance,
+            r_squared: fit.r_squared,
+            degrees_of_freedom: fit.points - 2,
+        })
+    }
+
+    /// Straight-line fit of arrival time on energy under the configured loss
+    ///
+    /// Huber loss refits with each photon's weight scaled by its robust weight until
+    /// the slope and intercept settle to within `IRLS_TOLERANCE` of their errors.
+    fn linear_fit<'a, I>(&self, measurements: I) -> Option<LinearFit>
+    where
+        I: IntoIterator<Item = &'a TimingData>,
+    {
+        let measurements: Vec<&TimingData> = measurements.into_iter().collect();
+        let mut fit = weighted_linear_fit(measurements.iter().copied())?;
+        if self.loss == LossKind::LeastSquares {
+            return Some(fit);
+        }
+        for _ in 0..IRLS_MAX_ITERATIONS {
+            let reweighted: Vec<TimingData> = measurements
+                .iter()
+                .map(|m| {
+                    let residual = m.arrival_time - fit.intercept - fit.slope * m.energy;
+                    let factor = self.loss.weight(residual / m.weighted_error());
+                    TimingData { weight: Some(m.weight_factor() * factor), ..(*m).clone() }
+                })
+                .collect();
+            let next = weighted_linear_fit(reweighted.iter())?;
+            let converged = (next.slope - fit.slope).abs()
+                <= IRLS_TOLERANCE * next.slope_variance.sqrt()
+                && (next.intercept - fit.intercept).abs()
+                    <= IRLS_TOLERANCE * next.intercept_variance.sqrt();
+            fit = next;
+            if converged {
+                break;
+            }
+        }
+        Some(fit)
+    }
+
+    /// Statistical error combined in quadrature with the systematic error
+    fn total_error(&self, error: f64) -> f64 {
+        if self.systematic_error == 0.0 {
+            error
+        } else {
+            error.hypot(self.systematic_error)
+        }
+    }
+
+    /// Total error on the side of the arrival time selected by `residual`
+    fn residual_error(&self, measurement: &TimingData, residual: f64) -> f64 {
+        self.total_error(measurement.error_for_residual(residual))
+    }
+
+    /// Weight `photon weight / total error²`, or `None` if the total error is not
+    /// positive and finite
+    fn inverse_variance_weight(&self, measurement: &TimingData) -> Option<f64> {
+        let error = self.total_error(measurement.error);
+        (error.is_finite() && error > 0.0)
+            .then(|| self.photon_weight(measurement) / (error * error))
+    }
+
+    /// Measurements with their errors replaced by the total errors and their weights
+    /// by the area-corrected photon weights, for fits
+    fn weighting_measurements(&self) -> Cow<'_, [TimingData]> {
+        if self.systematic_error == 0.0 && self.effective_area.is_none() {
+            return Cow::Borrowed(&self.measurements);
+        }
+        Cow::Owned(
+            self.measurements
+                .iter()
+                .map(|m| TimingData {
+                    error: self.total_error(m.error),
+                    error_low: m.error_low.map(|e| self.total_error(e)),
+                    error_high: m.error_high.map(|e| self.total_error(e)),
+                    weight: Some(self.photon_weight(m)),
+                    ..m.clone()
+                })
+                .collect(),
+        )
+    }
+
+    /// Arrival time minus the light-travel time, or the arrival time if the geometry is unknown
+    fn constant_c_residual(&self, measurement: &TimingData) -> f64 {
+        measurement.arrival_time - self.travel_time(measurement).unwrap_or(0.0)
+    }
+
+    /// Quantiles of the constant-c residuals (see `energy_time_correlation`)
+    ///
+    /// Each `q` in [0, 1] is interpolated linearly between the neighbouring order
+    /// statistics, so 0 and 1 give the smallest and largest residual. Results follow
+    /// the order of `qs`; with no measurements every quantile is NaN.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any `q` lies outside [0, 1].
+    pub fn residual_quantiles(&self, qs: &[f64]) -> Vec<f64> {
+        if let Some(q) = qs.iter().find(|q| !(0.0..=1.0).contains(*q)) {
+            panic!("quantiles must be in [0, 1], got {}", q);
+        }
+
+        let mut residuals: Vec<f64> = self
+            .measurements
+            .iter()
+            .map(|m| self.constant_c_residual(m))
+            .collect();
+        residuals.sort_by(|a, b| a.total_cmp(b));
+        qs.iter().map(|&q| percentile_sorted(&residuals, q)).collect()
+    }
+
+    /// Deviations from the expected arrivals paired with their inverse-variance weights
+    fn weighted_deviations(&self) -> Vec<(f64, f64)> {
+        self.measurements
+            .iter()
+            .zip(self.calculate_expected_arrivals())
+            .filter_map(|(m, expected)| {
+                self.inverse_variance_weight(m).map(|w| (m.arrival_time - expected.1, w))
+            })
+            .collect()
+    }
+
+    /// Inverse-variance weighted median of the deviations from the expected arrivals
+    ///
+    /// Unlike the weighted mean behind `LightSpeedTestResult::deviation_estimate`, a
+    /// few outlying photons cannot drag it far. Measurements without a usable error
+    /// are skipped; returns `None` if none remain.
+    pub fn weighted_median_residual(&self) -> Option<f64> {
+        weighted_median(&mut self.weighted_deviations())
+    }
+
+    /// Weighted median absolute deviation of the residuals about their weighted median
+    ///
+    /// Uses the weights of `weighted_median_residual`. The raw MAD is returned; multiply
+    /// by 1.4826 for a standard deviation estimate under Gaussian noise.
+    pub fn residual_mad(&self) -> Option<f64> {
+        let mut deviations = self.weighted_deviations();
+        let median = weighted_median(&mut deviations.clone())?;
+        for (deviation, _) in &mut deviations {
+            *deviation = (*deviation - median).abs();
+        }
+        weighted_median(&mut deviations)
+    }
+
+    /// Fits a two-component Gaussian mixture to the constant-c residuals by EM
+    ///
+    /// The components start from the lower and upper halves of the sorted residuals and
+    /// are refined by expectation-maximization until the log-likelihood changes by less
+    /// than 1e-10 per residual. Components are reported in order of increasing mean.
+    /// Returns `None` with fewer than four measurements, if a component collapses to
+    /// zero variance or weight, or if the fit has not converged after 1000 iterations.
+    pub fn fit_two_gaussian_mixture(&self) -> Option<MixtureResult> {
+        let mut residuals: Vec<f64> = self
+            .measurements
+            .iter()
+            .map(|m| self.constant_c_residual(m))
+            .collect();
+        if residuals.len() < 4 || residuals.iter().any(|r| !r.is_finite()) {
+            return None;
+        }
+        residuals.sort_by(|a, b| a.total_cmp(b));
+        let n = residuals.len() as f64;
+
+        let moments = |values: &[f64]| {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+            (mean, variance)
+        };
+        let (lower, upper) = residuals.split_at(residuals.len() / 2);
+        let (mean0, variance0) = moments(lower);
+        let (mean1, variance1) = moments(upper);
+        let mut means = [mean0, mean1];
+        let mut variances = [variance0, variance1];
+        let mut weights = [0.5, 0.5];
+
+        let density = |x: f64, mean: f64, variance: f64| {
+            (-(x - mean).powi(2) / (2.0 * variance)).exp()
+                / (2.0 * std::f64::consts::PI * variance).sqrt()
+        };
+        let mut previous_log_likelihood = f64::NEG_INFINITY;
+        for iteration in 0..MIXTURE_MAX_ITERATIONS {
+            if variances.iter().chain(&weights).any(|&v| v <= 0.0) {
+                return None;
+            }
+            
+            // E step: responsibility of the second component for each residual
+            let mut log_likelihood = 0.0;
+            let responsibilities: Vec<f64> = residuals
+                .iter()
+                .map(|&x| {
+                    let p0 = weights[0] * density(x, means[0], variances[0]);
+                    let p1 = weights[1] * density(x, means[1], variances[1]);
+                    log_likelihood += (p0 + p1).ln();
+                    if p0 + p1 > 0.0 { p1 / (p0 + p1) } else { 0.5 }
+                })
+                .collect();
+            if !log_likelihood.is_finite() {
+                return None;
+            }
+            if iteration > 0
+                && (log_likelihood - previous_log_likelihood).abs() < MIXTURE_TOLERANCE * n
+            {
+                return Some(MixtureResult { means, variances, weights });
+            }
+            previous_log_likelihood = log_likelihood;
+            
+            // M step
+            for component in 0..2 {
+                let share = |r: f64| if component == 1 { r } else { 1.0 - r };
+                let total: f64 = responsibilities.iter().map(|&r| share(r)).sum();
+                if total <= 0.0 {
+                    return None;
+                }
+                let mean = residuals
+                    .iter()
+                    .zip(&responsibilities)
+                    .map(|(&x, &r)| share(r) * x)
+                    .sum::<f64>()
+                    / total;
+                let variance = residuals
+                    .iter()
+                    .zip(&responsibilities)
+                    .map(|(&x, &r)| share(r) * (x - mean).powi(2))
+                    .sum::<f64>()
+                    / total;
+                means[component] = mean;
+                variances[component] = variance;
+                weights[component] = total / n;
+            }
+            if means[1] < means[0] {
+                means.swap(0, 1);
+                variances.swap(0, 1);
+                weights.swap(0, 1);
+            }
+        }
+        None
+    }
+
+    /// Spreads photons recorded at identical arrival times within the time resolution
+    ///
+    /// The first photon at each time keeps it; every later photon at the same time is
+    /// moved by a seeded uniform offset in `(-jitter / 2, jitter / 2)`, redrawn until the
+    /// new time is distinct from every other; a photon whose time cannot be moved at
+    /// `f64` precision within the jitter stays tied. Use this before methods that
+    /// assume continuous, untied times: `SpeedOfLightHypothesisTest::mann_whitney_u`
+    /// averages the ranks of ties, which costs power, and `ks_normality_test` and
+    /// `shapiro_wilk` assume residuals from a continuous distribution. Returns the
+    /// number of photons moved; the same `seed` moves them identically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `jitter` is not positive and finite.
+    pub fn deduplicate_times(&mut self, jitter: f64, seed: u64) -> usize {
+        assert!(
+            jitter.is_finite() && jitter > 0.0,
+            "jitter must be positive and finite, got {}",
+            jitter
+        );
+        // Adding zero folds -0.0 into 0.0 so equal times share a key
+        let key = |time: f64| (time + 0.0).to_bits();
+        let mut seen = HashSet::with_capacity(self.measurements.len());
+        let mut tied = Vec::new();
+        for (index, measurement) in self.measurements.iter().enumerate() {
+            let time = measurement.arrival_time;
+            if time.is_finite() && !seen.insert(key(time)) {
+                tied.push(index);
+            }
+        }
+        if tied.is_empty() {
+            return 0;
+        }
+
+        self.invalidate_cache();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let half_width = jitter / 2.0;
+        let mut moved = 0;
+        for &index in &tied {
+            let recorded = self.measurements[index].arrival_time;
+            let distinct = (0..DEDUPLICATION_MAX_DRAWS)
+                .map(|_| recorded + rng.gen_range(-half_width..half_width))
+                .find(|&candidate| seen.insert(key(candidate)));
+            if let Some(time) = distinct {
+                self.measurements[index].arrival_time = time;
+                moved += 1;
+            }
+        }
+        moved
+    }
+
+    /// Iteratively removes measurements more than `n_sigma` standard deviations from the mean
+    ///
+    /// Each pass computes the inverse-error² weighted mean and standard deviation of the
+    /// constant-c residuals (see `energy_time_correlation`) and drops every measurement
+    /// whose residual lies further than `n_sigma · σ` from the mean. Passes repeat until
+    /// none is removed. A pass that would leave fewer than three measurements is not
+    /// applied. Measurements without a usable error are never clipped. Returns the
+    /// number of measurements removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_sigma` is not a positive, finite number.
+    pub fn sigma_clip(&mut self, n_sigma: f64) -> usize {
+        assert!(
+            n_sigma.is_finite() && n_sigma > 0.0,
+            "clipping sigma must be positive and finite, got {}",
+            n_sigma
+        );
+
+        let initial = self.measurements.len();
+        loop {
+            let residuals: Vec<(f64, Option<f64>)> = self
+                .measurements
+                .iter()
+                .map(|m| (self.constant_c_residual(m), self.inverse_variance_weight(m)))
+                .collect();
+            let (sum, total_weight) = residuals
+                .iter()
+                .filter_map(|&(r, w)| w.map(|w| (w * r, w)))
+                .fold((0.0, 0.0), |(sum, total), (wr, w)| (sum + wr, total + w));
+            if total_weight <= 0.0 {
+                break;
+            }
+            let mean = sum / total_weight;
+            let variance = residuals
+                .iter()
+                .filter_map(|&(r, w)| w.map(|w| w * (r - mean) * (r - mean)))
+                .sum::<f64>()
+                / total_weight;
+            let limit = n_sigma * variance.sqrt();
+            
+            let keep: Vec<bool> = residuals
+                .iter()
+                .map(|&(r, w)| w.is_none() || (r - mean).abs() <= limit)
+                .collect();
+            let kept = keep.iter().filter(|&&k| k).count();
+            if kept == self.measurements.len() || kept < 3 {
+                break;
+            }
+            
+            let mut flags = keep.into_iter();
+            self.measurements.retain(|_| flags.next().unwrap_or(true));
+            self.invalidate_cache();
+        }
+
+        initial - self.measurements.len()
+    }
+
+    /// Pearson correlation between photon energy and arrival-time residual
+    ///
+    /// Residuals are taken from the constant-c model: the arrival time minus the
+    /// light-travel time when the source geometry is known, otherwise the arrival time
+    /// itself (a common emission time shifts every residual equally and cancels).
+    /// A significant nonzero value hints at energy-dependent dispersion. Returns `None`
+    /// for fewer than two points or when either quantity has zero variance.
+    pub fn energy_time_correlation(&self) -> Option<f64> {
+        let n = self.measurements.len();
+        if n < 2 {
+            return None;
+        }
+
+        let points: Vec<(f64, f64)> = self
+            .measurements
+            .iter()
+            .map(|m| (m.energy, self.constant_c_residual(m)))
+            .collect();
+        let mean_energy = points.iter().map(|p| p.0).sum::<f64>() / n as f64;
+        let mean_residual = points.iter().map(|p| p.1).sum::<f64>() / n as f64;
+
+        let (covariance, energy_variance, residual_variance) = points.iter().fold(
+            (0.0, 0.0, 0.0),
+            |(cov, var_e, var_r), &(energy, residual)| {
+                let de = energy - mean_energy;
+                let dr = residual - mean_residual;
+                (cov + de * dr, var_e + de * de, var_r + dr * dr)
+            },
+        );
+
+        let denominator = (energy_variance * residual_variance).sqrt();
+        (denominator > 0.0).then(|| covariance / denominator)
+    }
+
+    /// One-sided lower bound on the linear-order E_QG in GeV at the given confidence
+    ///
+    /// Combines the fitted LIV slope and its uncertainty with the light-travel time
+    /// shared by every measurement; see `LivFitResult::energy_scale_lower_bound`. A
+    /// single slope only maps to E_QG for one travel time, so this returns `None` if
+    /// any measurement lacks a source distance (or redshift, with a cosmology), if the
+    /// measurements come from sources at different distances, or if the fit is
+    /// undefined. For pooled sources, call `LivFitResult::energy_scale_lower_bound`
+    /// with the travel time to quote.
+    pub fn quantum_gravity_energy_bound(&self, confidence: f64) -> Option<f64> {
+        let mut travel_times = self.measurements.iter().map(|m| self.travel_time(m));
+        let travel_time = travel_times.next()??;
+        if !travel_times.all(|t| t == Some(travel_time)) {
+            return None;
+        }
+        self.fit_liv_slope().energy_scale_lower_bound(travel_time, confidence)
+    }
+
+    /// Smallest LIV delay slope in s/GeV the dataset could detect at `confidence`
+    ///
+    /// Assumes no signal: the slope error of the weighted least-squares fit in
+    /// `fit_liv_slope` depends only on the energies and timing errors, so the forecast is
+    /// `z · σ_slope` with `z` the one-sided standard normal quantile at `confidence`.
+    /// Dividing a light-travel time by the forecast gives the linear-order E_QG reach, so
+    /// a wider energy lever arm or smaller errors raise the reach. Returns `None` when the
+    /// slope cannot be fitted or `confidence` is not in (0.5, 1).
+    pub fn sensitivity_forecast(&self, confidence: f64) -> Option<f64> {
+        if !(confidence > 0.5 && confidence < 1.0) {
+            return None;
+        }
+        let fit = weighted_linear_fit(self.weighting_measurements().iter())?;
+        Some(standard_normal_quantile(confidence) * fit.slope_variance.sqrt())
+    }
+
+    /// Groups photons into energy bins and reports the weighted-mean arrival time per bin
+    ///
+    /// Bins are half-open `[low, high)` except the last, which includes its upper edge.
+    /// Photons outside the edges are dropped and counted in `SpectralLag::dropped`.
+    /// Returns an error unless there are at least two strictly increasing, finite edges.
+    pub fn spectral_lag(&self, bin_edges: &[f64]) -> Result<SpectralLag, String> {
+        validate_bin_edges(bin_edges)?;
+
+        let bin_count = bin_edges.len() - 1;
+        let mut counts = vec![0usize; bin_count];
+        let mut weighted_sums = vec![0.0; bin_count];
+        let mut total_weights = vec![0.0; bin_count];
+        let mut dropped = 0;
+
+        for measurement in &self.measurements {
+            let bin = match bin_index(bin_edges, measurement.energy) {
+                Some(bin) => bin,
+                None => {
+                    dropped += 1;
+                    continue;
+                }
+            };
+            counts[bin] += 1;
+            if let Some(weight) = self.inverse_variance_weight(measurement) {
+                weighted_sums[bin] += weight * measurement.arrival_time;
+                total_weights[bin] += weight;
+            }
+        }
+
+        let bins = (0..bin_count)
+            .map(|bin| {
+                let total_weight = total_weights[bin];
+                let (mean_arrival_time, mean_arrival_error) = if total_weight > 0.0 {
+                    (weighted_sums[bin] / total_weight, 1.0 / total_weight.sqrt())
+                } else {
+                    (f64::NAN, f64::INFINITY)
+                };
+                SpectralLagBin {
+                    energy_low: bin_edges[bin],
+                    energy_high: bin_edges[bin + 1],
+                    count: counts[bin],
+                    mean_arrival_time,
+                    mean_arrival_error,
+                }
+            })
+            .collect();
 
-impl GammaRayAnalyzer {
-    /// Creates a new analyzer with default settings
-    pub fn new() -> Self {
-        Self {
-            measurements: Vec::new(),
-            sensitivity_threshold: 1e-12,
-            quantum_gravity_model: None,
+        Ok(SpectralLag { bins, dropped })
+    }
+
+    /// Summarizes the photons in each energy band
+    ///
+    /// Bands follow the same `[low, high)` convention as `spectral_lag`, with the last
+    /// band closed and photons outside the edges ignored. Each band reports its photon
+    /// count and mean energy, the inverse-variance weighted mean arrival time, and the
+    /// RMS of the constant-c residuals about their own weighted mean. Statistics of an
+    /// empty band, or of one without usable errors, are NaN.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless there are at least two strictly increasing, finite edges.
+    pub fn energy_band_summary(&self, edges: &[f64]) -> Vec<BandSummary> {
+        if let Err(message) = validate_bin_edges(edges) {
+            panic!("{}", message);
+        }
+
+        let mut bands: Vec<Vec<&TimingData>> = vec![Vec::new(); edges.len() - 1];
+        for measurement in &self.measurements {
+            if let Some(bin) = bin_index(edges, measurement.energy) {
+                bands[bin].push(measurement);
+            }
+        }
+
+        bands
+            .iter()
+            .enumerate()
+            .map(|(bin, members)| {
+                let count = members.len();
+                let mean_energy = members.iter().map(|m| m.energy).sum::<f64>() / count as f64;
+                
+                let weighted_mean = |value: &dyn Fn(&TimingData) -> f64| {
+                    let (sum, total) = members
+                        .iter()
+                        .filter_map(|m| self.inverse_variance_weight(m).map(|w| (w * value(m), w)))
+                        .fold((0.0, 0.0), |(sum, total), (wv, w)| (sum + wv, total + w));
+                    if total > 0.0 { sum / total } else { f64::NAN }
+                };
+                let weighted_mean_arrival = weighted_mean(&|m| m.arrival_time);
+                let residual_mean = weighted_mean(&|m| self.constant_c_residual(m));
+                let residual_rms = (members
+                    .iter()
+                    .map(|m| (self.constant_c_residual(m) - residual_mean).powi(2))
+                    .sum::<f64>()
+                    / count as f64)
+                    .sqrt();
+                
+                BandSummary {
+                    energy_low: edges[bin],
+                    energy_high: edges[bin + 1],
+                    count,
+                    mean_energy,
+                    weighted_mean_arrival,
+                    residual_rms,
+                }
+            })
+            .collect()
+    }
+
+    /// Splits the light speed test chi-squared across energy bins
+    ///
+    /// Returns `(bin_low, bin_high, chi_squared)` per bin, where each bin sums the
+    /// chi-squared terms of its photons under the configured loss. Bins follow the
+    /// `[low, high)` convention of `spectral_lag` with the last bin closed; photons
+    /// outside the edges are ignored, so the contributions add up to
+    /// `test_light_speed_constancy().chi_squared` when the edges span every energy.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless there are at least two strictly increasing, finite edges.
+    pub fn chi_squared_by_bin(&self, edges: &[f64]) -> Vec<(f64, f64, f64)> {
+        if let Err(message) = validate_bin_edges(edges) {
+            panic!("{}", message);
+        }
+
+        let mut terms = self.chi_squared_terms(self.calculate_expected_arrivals());
+        self.reweight_terms(&mut terms);
+
+        let mut sums = vec![CompensatedSum::default(); edges.len() - 1];
+        for (measurement, term) in self.measurements.iter().zip(&terms) {
+            if let Some(bin) = bin_index(edges, measurement.energy) {
+                sums[bin].add(term.0);
+            }
         }
+
+        sums.iter()
+            .enumerate()
+            .map(|(bin, sum)| (edges[bin], edges[bin + 1], sum.total()))
+            .collect()
     }
 
-    /// Adds a timing measurement to the dataset
-    pub fn add_measurement(&mut self, energy: f64, arrival_time: f64, error: f64) {
-        self.measurements.push(TimingData { energy, arrival_time, error });
+    /// Histograms arrival times into bins of `bin_width` seconds
+    ///
+    /// The first bin starts at the earliest arrival. Returns `(bin_start, count)` for
+    /// every bin up to the one holding the latest arrival, including empty bins, or an
+    /// empty vector when there are no measurements. Returns an error when the arrivals
+    /// span more than `MAX_LIGHT_CURVE_BINS` bins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bin_width` is not positive and finite.
+    pub fn binned_light_curve(&self, bin_width: f64) -> Result<Vec<(f64, usize)>, String> {
+        assert!(
+            bin_width.is_finite() && bin_width > 0.0,
+            "bin width must be positive and finite, got {}",
+            bin_width
+        );
+        if self.measurements.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let times = self.measurements.iter().map(|m| m.arrival_time);
+        let start = times.clone().fold(f64::INFINITY, f64::min);
+        let end = times.clone().fold(f64::NEG_INFINITY, f64::max);
+        let bin_count = light_curve_bin_count(start, end, bin_width)?;
+
+        let mut counts = vec![0usize; bin_count];
+        for t in times {
+            let bin = (((t - start) / bin_width).floor() as usize).min(bin_count - 1);
+            counts[bin] += 1;
+        }
+        Ok(counts
+            .into_iter()
+            .enumerate()
+            .map(|(bin, count)| (start + bin as f64 * bin_width, count))
+            .collect())
     }
 
-    /// Sets the sensitivity threshold for detecting deviations
-    pub fn set_sensitivity_threshold(&mut self, threshold: f64) {
-        self.sensitivity_threshold = threshold;
+    /// Standardized residuals `(arrival - expected) / error` in order of arrival time,
+    /// centred on their mean
+    fn time_ordered_residuals(&self) -> Vec<f64> {
+        let expected_times = self.calculate_expected_arrivals();
+        let mut ordered: Vec<(f64, f64)> = self
+            .measurements
+            .iter()
+            .zip(expected_times.iter())
+            .map(|(m, expected)| {
+                let deviation = m.arrival_time - expected.1;
+                (m.arrival_time, deviation / self.residual_error(m, deviation))
+            })
+            .collect();
+        ordered.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mean = ordered.iter().map(|&(_, r)| r).sum::<f64>() / ordered.len() as f64;
+        ordered.into_iter().map(|(_, r)| r - mean).collect()
     }
 
-    /// Sets up quantum gravity model simulation
-    pub fn enable_quantum_gravity_simulation(&mut self, model: QuantumGravityModel) {
-        self.quantum_gravity_model = Some(model);
+    /// Autocorrelation of the time-ordered residuals at lags 0 through `max_lag`
+    ///
+    /// Residuals are the deviations from the expected arrivals in units of their
+    /// errors, sorted by arrival time and centred on their mean. Element `k` is
+    /// `Σ r_t r_{t+k} / Σ r_t²`, so element 0 is 1 and independent residuals give values
+    /// near zero at every other lag. Lags with no pairs of residuals, and every lag when
+    /// the residuals have no spread, are NaN.
+    pub fn residual_autocorrelation(&self, max_lag: usize) -> Vec<f64> {
+        let residuals = self.time_ordered_residuals();
+        let variance = stable_sum(residuals.iter().map(|r| r * r));
+        (0..=max_lag)
+            .map(|lag| {
+                if lag >= residuals.len() || variance <= 0.0 {
+                    return f64::NAN;
+                }
+                let covariance = stable_sum(
+                    residuals.iter().zip(&residuals[lag..]).map(|(a, b)| a * b),
+                );
+                covariance / variance
+            })
+            .collect()
     }
 
-    /// Performs analysis to test light speed constancy hypothesis
-    pub fn test_light_speed_constancy(&self) -> LightSpeedTestResult {
+    /// Durbin–Watson statistic of the time-ordered residuals
+    ///
+    /// `Σ (r_t - r_{t-1})² / Σ r_t²` over the residuals of `residual_autocorrelation`.
+    /// It is about `2 (1 - ρ₁)` for lag-1 autocorrelation `ρ₁`: near 2 for independent
+    /// residuals, towards 0 for positive and towards 4 for negative serial correlation.
+    /// NaN with fewer than two measurements or no spread in the residuals.
+    pub fn durbin_watson(&self) -> f64 {
+        let residuals = self.time_ordered_residuals();
+        let variance = stable_sum(residuals.iter().map(|r| r * r));
+        if residuals.len() < 2 || variance <= 0.0 {
+            return f64::NAN;
+        }
+        stable_sum(residuals.windows(2).map(|pair| (pair[1] - pair[0]).powi(2))) / variance
+    }
+
+    /// Histograms the constant-c residuals (see `energy_time_correlation`) into `bins`
+    /// equal-width bins spanning the smallest to the largest residual
+    ///
+    /// Returns `(bin_low, bin_high, count)` per bin, with the last bin closed on the
+    /// right so every measurement is counted. When all residuals are equal the range
+    /// is widened by 0.5 s on each side. Returns an empty vector with no measurements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn residual_histogram(&self, bins: usize) -> Vec<(f64, f64, usize)> {
+        assert!(bins >= 1, "bin count must be at least 1");
         if self.measurements.is_empty() {
-            return LightSpeedTestResult {
-                is_valid: true,
-                confidence_level: 0.0,
-                deviation_estimate: None,
-                chi_squared: 0.0,
-                p_value: 1.0,
-                anomalies_detected: 0,
-            };
+            return vec![];
         }
 
-        // Calculate expected arrival times assuming c = 299792458 m/s
-        let expected_times = self.calculate_expected_arrivals();
-        
-        // Calculate chi-squared statistic
-        let mut chi_squared = 0.0;
-        let mut total_weighted_deviation = 0.0;
-        let mut total_weight = 0.0;
-        
-        for (i, measurement) in self.measurements.iter().enumerate() {
-            if i < expected_times.len() {
-                let expected_time = expected_times[i].1;
-                let deviation = measurement.arrival_time - expected_time;
-                let weight = 1.0 / (measurement.error * measurement.error);
-                
-                chi_squared += weight * deviation * deviation;
-                total_weighted_deviation += weight * deviation;
-                total_weight += weight;
-            }
-        }
-        
-        let degrees_of_freedom = self.measurements.len().saturating_sub(1);
-        let p_value = if degrees_of_freedom > 0 {
-            // Simplified p-value calculation using chi-squared distribution
-            // In practice, this would use a proper statistical library
-            let chi_sq = chi_squared;
-            if chi_sq > 0.0 {
-                // Approximate p-value (very simplified)
-                1.0 - (-chi_sq / 2.0).exp()
-            } else {
-                1.0
+        let residuals: Vec<f64> =
+            self.measurements.iter().map(|m| self.constant_c_residual(m)).collect();
+        let mut low = residuals.iter().copied().fold(f64::INFINITY, f64::min);
+        let mut high = residuals.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if high == low {
+            low -= 0.5;
+            high += 0.5;
+        }
+        let width = (high - low) / bins as f64;
+
+        let mut counts = vec![0usize; bins];
+        for residual in residuals {
+            let bin = (((residual - low) / width).floor() as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(bin, count)| {
+                let bin_high = if bin + 1 == bins { high } else { low + (bin + 1) as f64 * width };
+                (low + bin as f64 * width, bin_high, count)
+            })
+            .collect()
+    }
+
+    /// Smooths a light curve with a centred moving average of `window` bins
+    ///
+    /// Near the ends the window is truncated to the bins available, so the total
+    /// count is preserved approximately rather than exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is even or not smaller than the number of bins.
+    pub fn smooth_light_curve(counts: &[f64], window: usize) -> Vec<f64> {
+        assert!(window % 2 == 1, "smoothing window must be odd, got {}", window);
+        assert!(
+            window < counts.len(),
+            "smoothing window {} must be smaller than the light curve length {}",
+            window,
+            counts.len()
+        );
+
+        let half = window / 2;
+        (0..counts.len())
+            .map(|i| {
+                let span = &counts[i.saturating_sub(half)..(i + half + 1).min(counts.len())];
+                span.iter().sum::<f64>() / span.len() as f64
+            })
+            .collect()
+    }
+
+    /// Indices of the peaks of a light curve with at least `min_prominence` prominence
+    ///
+    /// A peak is a bin, or a run of equal bins (a plateau), strictly higher than the
+    /// bins on either side; a plateau is reported at its middle bin. The first and last
+    /// bins cannot be peaks because only one side of them is known. Prominence is the
+    /// height above the higher of the two lowest points reached on each side before the
+    /// curve rises above the peak again or ends. Indices are returned in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_prominence` is negative or not finite.
+    pub fn find_peaks(counts: &[f64], min_prominence: f64) -> Vec<usize> {
+        assert!(
+            min_prominence.is_finite() && min_prominence >= 0.0,
+            "minimum prominence must be non-negative and finite, got {}",
+            min_prominence
+        );
+
+        let mut peaks = Vec::new();
+        let mut i = 1;
+        while i + 1 < counts.len() {
+            if counts[i] <= counts[i - 1] {
+                i += 1;
+                continue;
             }
-        } else {
-            1.0
-        };
-        
-        let confidence_level = 1.0 - p_value;
-        
-        // Estimate deviation
-        let deviation_estimate = if total_weight > 0.0 {
-            Some(total_weighted_deviation / total_weight)
-        } else {
-            None
-        };
-        
-        let anomalies = self.detect_anomalies();
-        
-        LightSpeedTestResult {
-            is_valid: p_value > 0.05, // 95% confidence level
-            confidence_level,
-            deviation_estimate,
-            chi_squared,
-            p_value,
-            anomalies_detected: anomalies.len(),
+            let mut end = i;
+            while end + 1 < counts.len() && counts[end + 1] == counts[i] {
+                end += 1;
+            }
+            if end + 1 < counts.len() && counts[end + 1] < counts[i] {
+                let height = counts[i];
+                let left_base = counts[..i]
+                    .iter()
+                    .rev()
+                    .take_while(|&&c| c <= height)
+                    .fold(f64::INFINITY, |low, &c| low.min(c));
+                let right_base = counts[end + 1..]
+                    .iter()
+                    .take_while(|&&c| c <= height)
+                    .fold(f64::INFINITY, |low, &c| low.min(c));
+                if height - left_base.max(right_base) >= min_prominence {
+                    peaks.push((i + end) / 2);
+                }
+            }
+            i = end + 1;
         }
+        peaks
     }
 
-    /// Calculates expected arrival times based on light speed assumption
-    pub fn calculate_expected_arrivals(&self) -> Vec<(f64, f64)> {
-        // Speed of light in vacuum (m/s)
-        const SPEED_OF_LIGHT: f64 = 299792458.0;
-        
-        // For simplicity, assume all photons travel the same distance
-        // In reality, this would depend on source distance and geometry
-        let base_distance = 1.0; // arbitrary base distance in meters
-        
-        self.measurements
+    /// Estimates the lag per unit energy by dispersion cancellation (DisCan)
+    ///
+    /// For `steps` trial lags evenly spaced over the inclusive `lag_range` (s/GeV), every
+    /// arrival time is de-dispersed to `t - lag · E` and histogrammed from the earliest
+    /// shifted time in bins of the original arrival span divided by `⌈√n⌉`. The
+    /// sharpness of each light curve is its Shannon information `Σ p ln p` over the
+    /// occupied bins, which is highest (zero) when every photon shares one bin. The
+    /// lag with the sharpest light curve is returned, preferring the smallest absolute
+    /// lag among ties. With fewer than two measurements or no spread in arrival time
+    /// the lag and information are NaN and the curve is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` is below two or `lag_range` is not a finite, increasing pair.
+    pub fn discan_estimate(&self, lag_range: (f64, f64), steps: usize) -> DisCanResult {
+        assert!(steps >= 2, "at least two lag steps are required, got {}", steps);
+        let (low, high) = lag_range;
+        assert!(
+            low.is_finite() && high.is_finite() && low < high,
+            "lag range must be finite and increasing, got ({}, {})",
+            low,
+            high
+        );
+
+        let times = self.measurements.iter().map(|m| m.arrival_time);
+        let span = times.clone().fold(f64::NEG_INFINITY, f64::max)
+            - times.fold(f64::INFINITY, f64::min);
+        let n = self.measurements.len();
+        if n < 2 || !span.is_finite() || span <= 0.0 {
+            return DisCanResult { lag: f64::NAN, information: f64::NAN, curve: vec![] };
+        }
+        let bin_width = span / (n as f64).sqrt().ceil();
+
+        let information_at = |lag: f64| {
+            let shifted: Vec<f64> =
+                self.measurements.iter().map(|m| m.arrival_time - lag * m.energy).collect();
+            let start = shifted.iter().copied().fold(f64::INFINITY, f64::min);
+            let mut bins: Vec<u64> =
+                shifted.iter().map(|t| ((t - start) / bin_width).floor() as u64).collect();
+            bins.sort_unstable();
+            bins.chunk_by(|a, b| a == b)
+                .map(|run| {
+                    let p = run.len() as f64 / n as f64;
+                    p * p.ln()
+                })
+                .sum::<f64>()
+        };
+
+        let step = (high - low) / (steps - 1) as f64;
+        let curve: Vec<(f64, f64)> = (0..steps)
+            .map(|i| {
+                let lag = if i + 1 == steps { high } else { low + i as f64 * step };
+                (lag, information_at(lag))
+            })
+            .collect();
+        let (lag, information) = curve
             .iter()
-            .map(|measurement| {
-                // Time delay due to energy-dependent propagation (if quantum gravity model is active)
-                let time_delay = if let Some(ref model) = self.quantum_gravity_model {
-                    // Simplified quantum gravity effect: delay proportional to energy^2
-                    let energy_gev = measurement.energy;
-                    model.quantum_gravity_effect * energy_gev * energy_gev / (SPEED_OF_LIGHT * SPEED_OF_LIGHT)
+            .copied()
+            .reduce(|best, trial| {
+                if trial.1 > best.1 || (trial.1 == best.1 && trial.0.abs() < best.0.abs()) {
+                    trial
                 } else {
-                    0.0
-                };
-                
-                // Expected arrival time (base + delay)
-                let expected_time = measurement.arrival_time - time_delay;
-                (measurement.energy, expected_time)
+                    best
+                }
             })
-            .collect()
+            .expect("at least two lag steps");
+        DisCanResult { lag, information, curve }
+    }
+
+    /// Finds the lag between two energy bands by cross-correlating their light curves
+    ///
+    /// Photons with energies inside each inclusive band are histogrammed into time bins of
+    /// `bin_width` seconds on a common grid. The mean-subtracted curves are correlated for
+    /// every whole-bin lag up to `max_lag`; a positive lag means the high band arrives later.
+    /// If either band is empty or has a flat light curve, the lag and correlation are zero.
+    /// Returns an error when the photons span more than `MAX_LIGHT_CURVE_BINS` bins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bin_width` is not positive and finite, or `max_lag` is negative or not finite.
+    pub fn cross_correlation_lag(
+        &self,
+        low_band: (f64, f64),
+        high_band: (f64, f64),
+        bin_width: f64,
+        max_lag: f64,
+    ) -> Result<CrossCorrelationResult, String> {
+        assert!(
+            bin_width.is_finite() && bin_width > 0.0,
+            "bin width must be positive and finite, got {}",
+            bin_width
+        );
+        assert!(
+            max_lag.is_finite() && max_lag >= 0.0,
+            "maximum lag must be non-negative and finite, got {}",
+            max_lag
+        );
+
+        let in_band = |m: &&TimingData, band: (f64, f64)| m.energy >= band.0 && m.energy <= band.1;
+        let low_times: Vec<f64> = self.measurements.iter()
+            .filter(|m| in_band(m, low_band))
+            .map(|m| m.arrival_time)
+            .collect();
+        let high_times: Vec<f64> = self.measurements.iter()
+            .filter(|m| in_band(m, high_band))
+            .map(|m| m.arrival_time)
+            .collect();
+
+        let mut result = CrossCorrelationResult {
+            lag: 0.0,
+            correlation: 0.0,
+            low_count: low_times.len(),
+            high_count: high_times.len(),
+        };
+        if low_times.is_empty() || high_times.is_empty() {
+            return Ok(result);
+        }
+
+        let all_times = low_times.iter().chain(&high_times);
+        let start = all_times.clone().fold(f64::INFINITY, |a, &b| a.min(b));
+        let end = all_times.fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let bin_count = light_curve_bin_count(start, end, bin_width)?;
+
+        let light_curve = |times: &[f64]| {
+            let mut counts = vec![0.0; bin_count];
+            for &t in times {
+                let bin = (((t - start) / bin_width).floor() as usize).min(bin_count - 1);
+                counts[bin] += 1.0;
+            }
+            let mean = counts.iter().sum::<f64>() / bin_count as f64;
+            counts.iter_mut().for_each(|c| *c -= mean);
+            counts
+        };
+        let low_curve = light_curve(&low_times);
+        let high_curve = light_curve(&high_times);
+
+        let norm = (low_curve.iter().map(|c| c * c).sum::<f64>()
+            * high_curve.iter().map(|c| c * c).sum::<f64>())
+        .sqrt();
+        if norm == 0.0 {
+            return Ok(result);
+        }
+
+        let max_shift = ((max_lag / bin_width).floor() as usize).min(bin_count - 1) as isize;
+        let mut best: Option<(isize, f64)> = None;
+        for shift in -max_shift..=max_shift {
+            let correlation = (0..bin_count as isize)
+                .filter_map(|i| {
+                    let j = i + shift;
+                    (j >= 0 && j < bin_count as isize)
+                        .then(|| low_curve[i as usize] * high_curve[j as usize])
+                })
+                .sum::<f64>()
+                / norm;
+            // Prefer the smallest absolute lag among equal peaks
+            let better = match best {
+                None => true,
+                Some((best_shift, best_value)) => {
+                    correlation > best_value
+                        || (correlation == best_value && shift.abs() < best_shift.abs())
+                }
+            };
+            if better {
+                best = Some((shift, correlation));
+            }
+        }
+
+        if let Some((shift, correlation)) = best {
+            result.lag = shift as f64 * bin_width;
+            result.correlation = correlation;
+        }
+        Ok(result)
     }
 
     /// Detects timing anomalies in the data
+    ///
+    /// Fewer than two measurements leave nothing to compare against, so none are flagged.
     pub fn detect_anomalies(&self) -> Vec<AnomalyDetectionResult> {
-        if self.measurements.is_empty() {
+        if self.measurements.len() < 2 {
             return vec![];
         }
-        
+
         let expected_times = self.calculate_expected_arrivals();
         let mut anomalies = Vec::new();
-        
+
         for (i, measurement) in self.measurements.iter().enumerate() {
             if i < expected_times.len() {
                 let expected_time = expected_times[i].1;
                 let deviation = measurement.arrival_time - expected_time;
-                let significance = deviation.abs() / measurement.error;
+                let significance = deviation.abs() / self.residual_error(measurement, deviation);
                 
-                if significance > 3.0 { // 3 sigma threshold
+                if significance > self.anomaly_sigma {
                     anomalies.push(AnomalyDetectionResult {
                         energy: measurement.energy,
                         measured_time: measurement.arrival_time,
                         expected_time,
                         deviation,
                         significance,
+                        kind: AnomalyKind::from_deviation(deviation),
                     });
                 }
             }
         }
-        
+
+        anomalies
+    }
+
+    /// The `n` most significant anomalies, most significant first
+    ///
+    /// Anomalies are those of `detect_anomalies`. Equal significances are ordered by
+    /// ascending energy and then by measurement order, so the selection is deterministic.
+    pub fn top_anomalies(&self, n: usize) -> Vec<AnomalyDetectionResult> {
+        let mut anomalies = self.detect_anomalies();
+        // A stable sort keeps measurement order as the final tie-break
+        anomalies.sort_by(|a, b| {
+            b.significance
+                .total_cmp(&a.significance)
+                .then(a.energy.total_cmp(&b.energy))
+        });
+        anomalies.truncate(n);
         anomalies
     }
+
+    /// Detects anomalies as the photons whose deviations are largest in magnitude
+    ///
+    /// A robust alternative to `detect_anomalies` that makes no Gaussian assumption:
+    /// a photon is flagged when its absolute deviation from the expected arrival
+    /// exceeds the `upper_pct` percentile of all absolute deviations, interpolated as
+    /// in `residual_quantiles`. Significances are still reported in units of the
+    /// measurement error. Results are in measurement order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `upper_pct` is not in (0, 100).
+    pub fn detect_anomalies_percentile(&self, upper_pct: f64) -> Vec<AnomalyDetectionResult> {
+        assert!(
+            upper_pct > 0.0 && upper_pct < 100.0,
+            "percentile must be in (0, 100), got {}",
+            upper_pct
+        );
+
+        let expected_times = self.calculate_expected_arrivals();
+        let deviations: Vec<f64> = self
+            .measurements
+            .iter()
+            .zip(expected_times.iter())
+            .map(|(m, expected)| m.arrival_time - expected.1)
+            .collect();
+        let mut magnitudes: Vec<f64> = deviations.iter().map(|d| d.abs()).collect();
+        magnitudes.sort_by(|a, b| a.total_cmp(b));
+        let threshold = percentile_sorted(&magnitudes, upper_pct / 100.0);
+
+        self.measurements
+            .iter()
+            .zip(expected_times.iter())
+            .zip(deviations)
+            .filter(|(_, deviation)| deviation.abs() > threshold)
+            .map(|((measurement, expected), deviation)| AnomalyDetectionResult {
+                energy: measurement.energy,
+                measured_time: measurement.arrival_time,
+                expected_time: expected.1,
+                deviation,
+                significance: deviation.abs() / self.residual_error(measurement, deviation),
+                kind: AnomalyKind::from_deviation(deviation),
+            })
+            .collect()
+    }
+
+    /// Detects anomalies against a robust estimate of the residual scatter
+    ///
+    /// A photon is flagged when its deviation lies more than the anomaly threshold
+    /// (see `set_anomaly_sigma`) from `weighted_median_residual`, in units of
+    /// `1.4826 · residual_mad`, which is reported as its significance. Outliers thus
+    /// cannot mask themselves by inflating the scale, and underestimated errors do not
+    /// flag every photon. When more than half the deviations coincide the MAD is zero,
+    /// and each photon's own error is used as the scale instead. Results are in
+    /// measurement order; empty if no measurement has a usable error.
+    pub fn detect_anomalies_robust(&self) -> Vec<AnomalyDetectionResult> {
+        let (median, mad) = match (self.weighted_median_residual(), self.residual_mad()) {
+            (Some(median), Some(mad)) => (median, mad),
+            _ => return vec![],
+        };
+        let scale = MAD_TO_SIGMA * mad;
+
+        self.measurements
+            .iter()
+            .zip(self.calculate_expected_arrivals())
+            .filter(|(m, _)| self.inverse_variance_weight(m).is_some())
+            .filter_map(|(measurement, expected)| {
+                let deviation = measurement.arrival_time - expected.1;
+                let offset = deviation - median;
+                let significance = if scale > 0.0 {
+                    offset.abs() / scale
+                } else {
+                    offset.abs() / self.residual_error(measurement, offset)
+                };
+                (significance > self.anomaly_sigma).then(|| AnomalyDetectionResult {
+                    energy: measurement.energy,
+                    measured_time: measurement.arrival_time,
+                    expected_time: expected.1,
+                    deviation,
+                    significance,
+                    kind: AnomalyKind::from_deviation(deviation),
+                })
+            })
+            .collect()
+    }
+
+    /// Detects timing anomalies and splits them into early and late arrivals
+    ///
+    /// Returns `(early, late)`, each in measurement order.
+    pub fn detect_anomalies_by_kind(
+        &self,
+    ) -> (Vec<AnomalyDetectionResult>, Vec<AnomalyDetectionResult>) {
+        self.detect_anomalies()
+            .into_iter()
+            .partition(|anomaly| anomaly.kind == AnomalyKind::EarlyArrival)
+    }
+}
+
+/// `n + 1` logarithmically spaced energy bin edges from `e_min` to `e_max`
+///
+/// Consecutive edges share the ratio `(e_max / e_min)^(1/n)`, and the first and last
+/// edges are exactly `e_min` and `e_max`. The edges suit `spectral_lag` and
+/// `energy_band_summary`.
+///
+/// # Panics
+///
+/// Panics unless `0 < e_min < e_max`, both are finite and `n` is at least 1.
+pub fn log_bin_edges(e_min: f64, e_max: f64, n: usize) -> Vec<f64> {
+    assert!(
+        e_min > 0.0 && e_min < e_max && e_max.is_finite(),
+        "energy bounds must satisfy 0 < e_min < e_max, got [{}, {}]",
+        e_min,
+        e_max
+    );
+    assert!(n >= 1, "bin count must be at least 1");
+    let log_ratio = (e_max / e_min).ln();
+    (0..=n)
+        .map(|i| match i {
+            0 => e_min,
+            i if i == n => e_max,
+            i => e_min * (log_ratio * i as f64 / n as f64).exp(),
+        })
+        .collect()
+}
+
+/// Number of `bin_width` bins needed to cover `[start, end]`
+///
+/// Returns an error instead of a count above `MAX_LIGHT_CURVE_BINS`, so a tiny width or
+/// a stray far-off timestamp cannot exhaust memory.
+fn light_curve_bin_count(start: f64, end: f64, bin_width: f64) -> Result<usize, String> {
+    let bins = ((end - start) / bin_width).floor() + 1.0;
+    if bins.is_nan() || bins > MAX_LIGHT_CURVE_BINS as f64 {
+        return Err(format!(
+            "{} s of arrivals in {} s bins exceeds the maximum of {} bins",
+            end - start,
+            bin_width,
+            MAX_LIGHT_CURVE_BINS
+        ));
+    }
+    Ok(bins as usize)
+}
+
+/// Checks that bin edges are finite, strictly increasing and define at least one bin
+fn validate_bin_edges(edges: &[f64]) -> Result<(), String> {
+    if edges.len() < 2 {
+        return Err(format!("at least two bin edges are required, got {}", edges.len()));
+    }
+    if edges.iter().any(|edge| !edge.is_finite()) {
+        return Err("bin edges must be finite".to_string());
+    }
+    if let Some(pair) = edges.windows(2).find(|pair| pair[1] <= pair[0]) {
+        return Err(format!(
+            "bin edges must be strictly increasing, got {} followed by {}",
+            pair[0], pair[1]
+        ));
+    }
+    Ok(())
+}
+
+/// Index of the bin containing `value`, with the last bin closed on the right
+fn bin_index(edges: &[f64], value: f64) -> Option<usize> {
+    let last = *edges.last()?;
+    if !(value >= edges[0] && value <= last) {
+        return None;
+    }
+    if value == last {
+        return Some(edges.len() - 2);
+    }
+    Some(edges.partition_point(|&edge| edge <= value) - 1)
+}
+
+impl Default for GammaRayAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Quantum gravity model parameters for photon propagation simulations
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QuantumGravityModel {
     pub planck_length: f64,
     pub quantum_gravity_effect: f64,
-    pub energy_scale: f64,
+    pub energy_scale: f64,           // Quantum gravity energy scale E_QG in GeV
+    pub dispersion_order: DispersionOrder,
+}
+
+/// Leading order of the energy dependence in the photon dispersion relation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum DispersionOrder {
+    /// Delay proportional to E / E_QG
+    Linear,
+    /// Delay proportional to (E / E_QG)²
+    Quadratic,
+}
+
+impl DispersionOrder {
+    /// Power `n` of the energy ratio in the delay formula
+    pub fn exponent(self) -> i32 {
+        match self {
+            DispersionOrder::Linear => 1,
+            DispersionOrder::Quadratic => 2,
+        }
+    }
+}
+
+impl QuantumGravityModel {
+    /// Energy-dependent propagation delay in seconds for a photon of `energy` GeV
+    ///
+    /// With a known light-travel time `d/c` this is `(1+n)/2 · (E/E_QG)^n · d/c`, which
+    /// reduces to `(E/E_QG) · d/c` in the linear case. Without a distance the delay
+    /// falls back to `quantum_gravity_effect · E^n / c^n`.
+    pub fn time_delay(&self, energy: f64, travel_time: Option<f64>) -> f64 {
+        let n = self.dispersion_order.exponent();
+        match travel_time {
+            Some(travel_time) => {
+                let prefactor = (1.0 + n as f64) / 2.0;
+                prefactor * (energy / self.energy_scale).powi(n) * travel_time
+            }
+            None => self.quantum_gravity_effect * (energy / SPEED_OF_LIGHT).powi(n),
+        }
+    }
 }
 
 /// Result of light speed constancy test
-#[derive(Debug, Clone)]
+///
+/// With fewer than two measurements there are no degrees of freedom to test against:
+/// `insufficient_data` is set, the result is valid with a p-value of 1 and a
+/// confidence level of 0, and no anomalies are reported. The chi-squared and the
+/// deviation estimate of a single measurement are still filled in.
+#[derive(Debug, Clone, Serialize)]
 pub struct LightSpeedTestResult {
     pub is_valid: bool,
     pub confidence_level: f64,
     pub deviation_estimate: Option<f64>,
+    pub deviation_error: Option<f64>,   // Standard error of the weighted-mean deviation
     pub chi_squared: f64,
+    pub degrees_of_freedom: usize,
+    pub reduced_chi_squared: f64,       // chi_squared / degrees_of_freedom, NaN without freedom
     pub p_value: f64,
     pub anomalies_detected: usize,
+    pub insufficient_data: bool,        // Fewer than two measurements, so nothing was tested
+}
+
+impl LightSpeedTestResult {
+    /// Trivially valid result reported when there is nothing to test
+    fn no_data() -> Self {
+        Self {
+            is_valid: true,
+            confidence_level: 0.0,
+            deviation_estimate: None,
+            deviation_error: None,
+            chi_squared: 0.0,
+            degrees_of_freedom: 0,
+            reduced_chi_squared: f64::NAN,
+            p_value: 1.0,
+            anomalies_detected: 0,
+            insufficient_data: true,
+        }
+    }
+
+    /// Builds the result from the accumulated chi-squared sums over `count` measurements
+    ///
+    /// The result is valid when the p-value exceeds `significance_level`. A single
+    /// measurement yields the degenerate result described on the type.
+    fn from_sums(
+        significance_level: f64,
+        count: usize,
+        chi_squared: f64,
+        total_weighted_deviation: f64,
+        total_weight: f64,
+        anomalies_detected: usize,
+    ) -> Self {
+        let degrees_of_freedom = count.saturating_sub(1);
+        let insufficient_data = degrees_of_freedom == 0;
+        let p_value = if insufficient_data {
+            1.0
+        } else {
+            chi_squared_pvalue(chi_squared, degrees_of_freedom)
+        };
+
+        let confidence_level = 1.0 - p_value;
+        let reduced_chi_squared = if !insufficient_data {
+            chi_squared / degrees_of_freedom as f64
+        } else {
+            f64::NAN
+        };
+
+        // Estimate deviation and its standard error
+        let (deviation_estimate, deviation_error) = if total_weight > 0.0 {
+            (Some(total_weighted_deviation / total_weight), Some(1.0 / total_weight.sqrt()))
+        } else {
+            (None, None)
+        };
+
+        Self {
+            is_valid: p_value > significance_level,
+            confidence_level,
+            deviation_estimate,
+            deviation_error,
+            chi_squared,
+            degrees_of_freedom,
+            reduced_chi_squared,
+            p_value,
+            anomalies_detected: if insufficient_data { 0 } else { anomalies_detected },
+            insufficient_data,
+        }
+    }
+}
+
+/// Running sums for `GammaRayAnalyzer::accumulate`
+#[derive(Debug, Clone, Default)]
+struct StreamingStatistics {
+    count: usize,
+    chi_squared: CompensatedSum,
+    weighted_deviation: CompensatedSum,
+    total_weight: CompensatedSum,
+    anomalies: usize,
+}
+
+/// Weighted least-squares fit of the LIV delay slope
+#[derive(Debug, Clone, Serialize)]
+pub struct LivFitResult {
+    pub slope: f64,                 // Delay slope in s/GeV
+    pub slope_error: f64,           // One-sigma standard error of the slope
+    pub reduced_chi_squared: f64,
+    pub energy_scale: Option<f64>,  // Linear-order E_QG in GeV, if the distance is known
+}
+
+/// Leave-one-out jackknife of the LIV delay slope
+#[derive(Debug, Clone, Serialize)]
+pub struct JackknifeResult {
+    pub mean_slope: f64,            // Mean of the leave-one-out slopes in s/GeV
+    pub standard_error: f64,        // Jackknife standard error of the slope
+    pub most_influential: usize,    // Index of the measurement whose removal moves the slope most
+}
+
+/// Weighted linear regression of arrival time on energy
+#[derive(Debug, Clone, Serialize)]
+pub struct RegressionResult {
+    pub slope: f64,                 // s/GeV
+    pub slope_error: f64,
+    pub intercept: f64,             // Arrival time at zero energy in seconds
+    pub intercept_error: f64,
+    pub slope_intercept_covariance: f64,
+    pub r_squared: f64,             // Weighted coefficient of determination
+    pub degrees_of_freedom: usize,  // Usable points minus the two fitted parameters
+}
+
+impl RegressionResult {
+    /// Two-sided confidence band of the fitted line at each energy
+    ///
+    /// Returns `(lower, upper)` bounds `intercept + slope · E ∓ t · σ(E)` where
+    /// `σ(E)² = σ_intercept² + 2E · cov + E² · σ_slope²` and `t` is the Student-t
+    /// critical value for `degrees_of_freedom`. A variance driven slightly negative by
+    /// rounding in a near-singular covariance is treated as zero; a non-finite variance,
+    /// or a fit with no degrees of freedom, gives NaN bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `confidence` is not in (0, 1).
+    pub fn confidence_band(&self, energies: &[f64], confidence: f64) -> Vec<(f64, f64)> {
+        assert!(
+            confidence > 0.0 && confidence < 1.0,
+            "confidence must be in (0, 1), got {}",
+            confidence
+        );
+        let critical = if self.degrees_of_freedom > 0 {
+            student_t_quantile(0.5 + confidence / 2.0, self.degrees_of_freedom as f64)
+        } else {
+            f64::NAN
+        };
+        energies
+            .iter()
+            .map(|&energy| {
+                let fitted = self.intercept + self.slope * energy;
+                let variance = self.intercept_error.powi(2)
+                    + 2.0 * energy * self.slope_intercept_covariance
+                    + energy * energy * self.slope_error.powi(2);
+                let half_width = if variance.is_finite() {
+                    critical * variance.max(0.0).sqrt()
+                } else {
+                    f64::NAN
+                };
+                (fitted - half_width, fitted + half_width)
+            })
+            .collect()
+    }
+}
+
+impl LivFitResult {
+    /// One-sided lower limit on the linear-order E_QG in GeV for the given travel time
+    ///
+    /// Uses the upper limit `|slope| + z·σ` on the delay slope, where `z` is the
+    /// standard normal quantile at `confidence` (1.645 at 0.95). Returns `None` when
+    /// the fit is undefined, the travel time is not positive or `confidence` is not
+    /// in [0.5, 1).
+    pub fn energy_scale_lower_bound(&self, travel_time: f64, confidence: f64) -> Option<f64> {
+        if !(0.5..1.0).contains(&confidence) {
+            return None;
+        }
+        let z = if confidence > 0.5 { standard_normal_quantile(confidence) } else { 0.0 };
+        let slope_limit = self.slope.abs() + z * self.slope_error;
+        (travel_time > 0.0 && slope_limit.is_finite() && slope_limit > 0.0)
+            .then(|| travel_time / slope_limit)
+    }
+}
+
+/// Arrival-time statistics for one energy bin
+#[derive(Debug, Clone, Serialize)]
+pub struct SpectralLagBin {
+    pub energy_low: f64,
+    pub energy_high: f64,
+    pub count: usize,
+    pub mean_arrival_time: f64,     // Inverse-variance weighted mean, NaN if empty
+    pub mean_arrival_error: f64,    // Standard error of the weighted mean
+}
+
+/// Photon statistics for one energy band
+#[derive(Debug, Clone, Serialize)]
+pub struct BandSummary {
+    pub energy_low: f64,
+    pub energy_high: f64,
+    pub count: usize,
+    pub mean_energy: f64,           // GeV
+    pub weighted_mean_arrival: f64, // Inverse-variance weighted mean arrival time
+    pub residual_rms: f64,          // RMS of the constant-c residuals about their weighted mean
+}
+
+/// Per-bin spectral-lag output
+#[derive(Debug, Clone, Serialize)]
+pub struct SpectralLag {
+    pub bins: Vec<SpectralLagBin>,
+    pub dropped: usize,             // Photons outside the bin edges
+}
+
+/// Two-component Gaussian mixture fitted to the constant-c residuals
+#[derive(Debug, Clone, Serialize)]
+pub struct MixtureResult {
+    pub means: [f64; 2],            // Component means in seconds, in increasing order
+    pub variances: [f64; 2],        // Component variances in s²
+    pub weights: [f64; 2],          // Mixing weights, summing to one
+}
+
+/// Peak of the cross-correlation between a low- and a high-energy light curve
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossCorrelationResult {
+    pub lag: f64,                   // High-band delay in seconds at the correlation peak
+    pub correlation: f64,           // Normalized cross-correlation at the peak
+    pub low_count: usize,
+    pub high_count: usize,
+}
+
+/// Dispersion-cancellation estimate of the lag per unit energy
+#[derive(Debug, Clone, Serialize)]
+pub struct DisCanResult {
+    pub lag: f64,                   // Lag in s/GeV whose de-dispersed light curve is sharpest
+    pub information: f64,           // Shannon information Σ p ln p at that lag
+    pub curve: Vec<(f64, f64)>,     // (trial lag, information) for every trial
 }
 
 /// Anomaly detection result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AnomalyDetectionResult {
     pub energy: f64,
     pub measured_time: f64,
     pub expected_time: f64,
     pub deviation: f64,
     pub significance: f64,
-}
\ No newline at end of file
+    pub kind: AnomalyKind,
+}
+
+/// Direction of an anomalous arrival relative to the expected time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnomalyKind {
+    /// Photon arrived before expected, as for superluminal propagation
+    EarlyArrival,
+    /// Photon arrived after expected, as for subluminal propagation
+    LateArrival,
+}
+
+impl AnomalyKind {
+    /// Classifies a signed deviation `measured - expected`
+    pub fn from_deviation(deviation: f64) -> Self {
+        if deviation < 0.0 {
+            AnomalyKind::EarlyArrival
+        } else {
+            AnomalyKind::LateArrival
+        }
+    }
+}
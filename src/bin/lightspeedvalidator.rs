@@ -1,13 +1,22 @@
+use clap::Parser;
 use lightspeedvalidator::{Cli, CliExecutor};
+use std::process::ExitCode;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() -> ExitCode {
     let cli = Cli::parse();
     
-    match &cli.command {
-        lightspeedvalidator::Commands::Analyze(args) => {
-            CliExecutor::execute(args.clone())?;
+    let outcome = match &cli.command {
+        lightspeedvalidator::Commands::Analyze(args) => CliExecutor::execute(args.clone()),
+        lightspeedvalidator::Commands::Report(args) => CliExecutor::report(args.clone()),
+        lightspeedvalidator::Commands::Compare(args) => CliExecutor::compare(args.clone()),
+        lightspeedvalidator::Commands::Simulate(args) => CliExecutor::simulate(args.clone()),
+    };
+    
+    match outcome {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(e.exit_code())
         }
     }
-    
-    Ok(())
 }
\ No newline at end of file
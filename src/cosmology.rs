@@ -0,0 +1,177 @@
+// src/cosmology.rs
+use serde::{Deserialize, Serialize};
+
+use crate::analyzer::{DispersionOrder, SPEED_OF_LIGHT};
+
+/// One megaparsec in meters
+pub const MEGAPARSEC_M: f64 = 3.085_677_581_491_367e22;
+
+/// Relative tolerance for the adaptive Simpson integration
+const INTEGRATION_TOLERANCE: f64 = 1e-10;
+/// Maximum recursion depth for the adaptive Simpson integration
+const INTEGRATION_MAX_DEPTH: usize = 50;
+
+/// Flat ΛCDM cosmology used to convert redshift into distance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cosmology {
+    pub hubble_constant: f64, // H0 in km/s/Mpc
+    pub omega_matter: f64,
+    pub omega_lambda: f64,
+}
+
+impl Cosmology {
+    /// Creates a cosmology from H0 (km/s/Mpc) and the matter and dark-energy densities
+    pub fn new(hubble_constant: f64, omega_matter: f64, omega_lambda: f64) -> Self {
+        Self { hubble_constant, omega_matter, omega_lambda }
+    }
+
+    /// Planck 2018 (TT,TE,EE+lowE+lensing+BAO) parameters
+    pub fn planck_2018() -> Self {
+        Self::new(67.4, 0.315, 0.685)
+    }
+
+    /// Hubble distance `c / H0` in meters
+    pub fn hubble_distance(&self) -> f64 {
+        (SPEED_OF_LIGHT / 1000.0) / self.hubble_constant * MEGAPARSEC_M
+    }
+
+    /// Dimensionless Hubble parameter `E(z) = H(z) / H0`
+    pub fn efunc(&self, z: f64) -> f64 {
+        let zp1 = 1.0 + z;
+        (self.omega_matter * zp1 * zp1 * zp1 + self.omega_lambda).sqrt()
+    }
+
+    /// Line-of-sight comoving distance to redshift `z` in meters
+    ///
+    /// Integrates `c / H(z')` from 0 to `z` with adaptive Simpson's rule.
+    pub fn comoving_distance(&self, z: f64) -> f64 {
+        if z <= 0.0 {
+            return 0.0;
+        }
+        self.hubble_distance() * adaptive_simpson(&|zp| 1.0 / self.efunc(zp), 0.0, z)
+    }
+
+    /// Tabulates the comoving distance on `n` equal redshift steps from 0 to `z_max`
+    ///
+    /// The integral is accumulated step by step, so building the table costs about as
+    /// much as one `comoving_distance(z_max)` call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `z_max` is not positive and finite or `n` is zero.
+    pub fn build_distance_table(&self, z_max: f64, n: usize) -> DistanceTable {
+        assert!(z_max > 0.0 && z_max.is_finite(), "z_max must be positive and finite");
+        assert!(n >= 1, "distance table needs at least one step");
+
+        let step = z_max / n as f64;
+        let hubble_distance = self.hubble_distance();
+        let mut distances = Vec::with_capacity(n + 1);
+        let mut integral = 0.0;
+        distances.push(0.0);
+        for i in 0..n {
+            let (low, high) = (i as f64 * step, (i + 1) as f64 * step);
+            integral += adaptive_simpson(&|zp| 1.0 / self.efunc(zp), low, high);
+            distances.push(hubble_distance * integral);
+        }
+        DistanceTable { cosmology: self.clone(), step, distances }
+    }
+
+    /// Jacob–Piran Lorentz-violating time delay in seconds for a photon from redshift `z`
+    ///
+    /// Evaluates `(1+n)/(2 H0) · (E/E_QG)^n · ∫₀^z (1+z')^n / E(z') dz'` for dispersion
+    /// order `n`, with `energy` and `e_qg` in the same units (GeV). The integral is
+    /// computed with adaptive Simpson's rule; non-positive redshifts give no delay.
+    pub fn liv_time_delay(&self, z: f64, energy: f64, e_qg: f64, order: DispersionOrder) -> f64 {
+        if z <= 0.0 {
+            return 0.0;
+        }
+        let n = order.exponent();
+        let hubble_time = self.hubble_distance() / SPEED_OF_LIGHT;
+        let integral = adaptive_simpson(&|zp: f64| (1.0 + zp).powi(n) / self.efunc(zp), 0.0, z);
+        (1.0 + n as f64) / 2.0 * (energy / e_qg).powi(n) * hubble_time * integral
+    }
+}
+
+impl Default for Cosmology {
+    fn default() -> Self {
+        Self::planck_2018()
+    }
+}
+
+/// Comoving distances precomputed on a redshift grid by `Cosmology::build_distance_table`
+#[derive(Debug, Clone)]
+pub struct DistanceTable {
+    cosmology: Cosmology,
+    step: f64,
+    distances: Vec<f64>,
+}
+
+impl DistanceTable {
+    /// Largest redshift covered by the grid
+    pub fn z_max(&self) -> f64 {
+        self.step * (self.distances.len() - 1) as f64
+    }
+
+    /// Comoving distance to redshift `z` in meters
+    ///
+    /// Inside the grid the distance is a cubic Hermite interpolation between the
+    /// neighbouring nodes, using the exact slope `c / H(z)` at each. Redshifts beyond
+    /// `z_max` fall back to `Cosmology::comoving_distance`; non-positive ones give 0.
+    pub fn interpolate(&self, z: f64) -> f64 {
+        if z <= 0.0 {
+            return 0.0;
+        }
+        if z.is_nan() || z > self.z_max() {
+            return self.cosmology.comoving_distance(z);
+        }
+        let index = ((z / self.step) as usize).min(self.distances.len() - 2);
+        let t = z / self.step - index as f64;
+        let step_distance = self.step * self.cosmology.hubble_distance();
+        let slope = |i: usize| step_distance / self.cosmology.efunc(i as f64 * self.step);
+        let (t2, t3) = (t * t, t * t * t);
+        (2.0 * t3 - 3.0 * t2 + 1.0) * self.distances[index]
+            + (t3 - 2.0 * t2 + t) * slope(index)
+            + (-2.0 * t3 + 3.0 * t2) * self.distances[index + 1]
+            + (t3 - t2) * slope(index + 1)
+    }
+}
+
+/// Integrates `f` over `[a, b]` with adaptive Simpson's rule
+pub(crate) fn adaptive_simpson<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64) -> f64 {
+    let fa = f(a);
+    let fb = f(b);
+    let m = 0.5 * (a + b);
+    let fm = f(m);
+    let whole = (b - a) / 6.0 * (fa + 4.0 * fm + fb);
+    let tolerance = INTEGRATION_TOLERANCE * whole.abs().max(f64::MIN_POSITIVE);
+    simpson_step(f, a, b, fa, fm, fb, whole, tolerance, INTEGRATION_MAX_DEPTH)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn simpson_step<F: Fn(f64) -> f64>(
+    f: &F,
+    a: f64,
+    b: f64,
+    fa: f64,
+    fm: f64,
+    fb: f64,
+    whole: f64,
+    tolerance: f64,
+    depth: usize,
+) -> f64 {
+    let m = 0.5 * (a + b);
+    let lm = 0.5 * (a + m);
+    let rm = 0.5 * (m + b);
+    let flm = f(lm);
+    let frm = f(rm);
+    let left = (m - a) / 6.0 * (fa + 4.0 * flm + fm);
+    let right = (b - m) / 6.0 * (fm + 4.0 * frm + fb);
+    let delta = left + right - whole;
+    
+    if depth == 0 || delta.abs() <= 15.0 * tolerance {
+        // Richardson extrapolation of the two-level estimate
+        return left + right + delta / 15.0;
+    }
+    simpson_step(f, a, m, fa, flm, fm, left, tolerance / 2.0, depth - 1)
+        + simpson_step(f, m, b, fm, frm, fb, right, tolerance / 2.0, depth - 1)
+}
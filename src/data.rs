@@ -1,39 +1,719 @@
-use std::path::Path;
-use csv::Reader;
-use crate::analyzer::TimingData;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use csv::ReaderBuilder;
+use serde::Serialize;
+use crate::analyzer::{AnalysisConfig, AnomalyDetectionResult, LightSpeedTestResult, TimingData};
+
+/// Errors produced while importing timing data
+///
+/// Line numbers are 1-based; column indices are 0-based.
+#[derive(Debug)]
+pub enum DataError {
+    /// The file could not be opened or read
+    Io(std::io::Error),
+    /// A field could not be parsed as a number
+    Parse { line: usize, column: usize },
+    /// A row is missing a required column
+    MissingColumn { line: usize, column: usize },
+    /// A row is structurally invalid or holds an unusable value
+    InvalidValue { line: usize, reason: String },
+    /// A JSON document could not be decoded
+    Json { path: PathBuf, source: serde_json::Error },
+    /// The input format could not be inferred from the file extension
+    UnknownFormat { path: PathBuf },
+    /// A column named in a `ColumnMap` is absent from the header row
+    MissingHeader { name: String },
+    /// A gzip-compressed file was given but the `gzip` feature is disabled
+    Compressed { path: PathBuf },
+    /// A FITS file could not be read
+    #[cfg(feature = "fits")]
+    Fits(fitsio::errors::Error),
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Io(e) => write!(f, "I/O error: {}", e),
+            DataError::Parse { line, column } => {
+                write!(f, "line {}, column {}: not a valid number", line, column)
+            }
+            DataError::MissingColumn { line, column } => {
+                write!(f, "line {}: missing column {}", line, column)
+            }
+            DataError::InvalidValue { line, reason } => write!(f, "line {}: {}", line, reason),
+            DataError::Json { path, source } => write!(f, "{}: {}", path.display(), source),
+            DataError::MissingHeader { name } => write!(f, "header has no column named {:?}", name),
+            DataError::Compressed { path } => write!(
+                f,
+                "{}: reading gzip-compressed input requires the `gzip` feature",
+                path.display()
+            ),
+            #[cfg(feature = "fits")]
+            DataError::Fits(e) => write!(f, "FITS error: {}", e),
+            DataError::UnknownFormat { path } => write!(
+                f,
+                "{}: cannot infer the input format from the extension; expected .csv, .json, .ndjson, .jsonl, .txt, .dat or .ascii",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DataError::Io(e) => Some(e),
+            DataError::Json { source, .. } => Some(source),
+            #[cfg(feature = "fits")]
+            DataError::Fits(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DataError {
+    fn from(e: std::io::Error) -> Self {
+        DataError::Io(e)
+    }
+}
+
+#[cfg(feature = "fits")]
+impl From<fitsio::errors::Error> for DataError {
+    fn from(e: fitsio::errors::Error) -> Self {
+        DataError::Fits(e)
+    }
+}
+
+impl From<csv::Error> for DataError {
+    fn from(e: csv::Error) -> Self {
+        let line = e.position().map_or(0, |p| p.line() as usize);
+        let reason = e.to_string();
+        match e.into_kind() {
+            csv::ErrorKind::Io(io) => DataError::Io(io),
+            _ => DataError::InvalidValue { line, reason },
+        }
+    }
+}
+
+/// Parses the numeric field at `column`, reporting `line` on failure
+fn parse_field(fields: &[&str], line: usize, column: usize) -> Result<f64, DataError> {
+    let field = fields
+        .get(column)
+        .ok_or(DataError::MissingColumn { line, column })?;
+    field.trim().parse().map_err(|_| DataError::Parse { line, column })
+}
+
+/// Parses the numeric field at `column` if a column is given and the row has a
+/// non-empty value there
+fn parse_optional_field(
+    fields: &[&str],
+    line: usize,
+    column: Option<usize>,
+) -> Result<Option<f64>, DataError> {
+    match column {
+        Some(column) if fields.get(column).is_some_and(|f| !f.trim().is_empty()) => {
+            parse_field(fields, line, column).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Splits a row-level error into its line and a reason, passing other errors through
+fn row_failure(error: DataError) -> Result<(usize, String), DataError> {
+    match error {
+        DataError::Parse { line, column } => {
+            Ok((line, format!("column {}: not a valid number", column)))
+        }
+        DataError::MissingColumn { line, column } => {
+            Ok((line, format!("missing column {}", column)))
+        }
+        DataError::InvalidValue { line, reason } => Ok((line, reason)),
+        other => Err(other),
+    }
+}
+
+/// How `CsvImporter` handles a row that cannot be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Stop at the first bad row and return its error
+    #[default]
+    Abort,
+    /// Skip bad rows, recording each in a `LoadReport`
+    SkipAndReport,
+}
+
+/// Rows skipped while loading under `ErrorPolicy::SkipAndReport`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// `(line, reason)` for each skipped row, in file order
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// Energy unit of an input file, converted to the canonical GeV at load time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnergyUnit {
+    MeV,
+    #[default]
+    GeV,
+    TeV,
+}
+
+impl EnergyUnit {
+    /// Number of GeV in one unit
+    pub fn gev_factor(self) -> f64 {
+        match self {
+            EnergyUnit::MeV => 1e-3,
+            EnergyUnit::GeV => 1.0,
+            EnergyUnit::TeV => 1e3,
+        }
+    }
+
+    /// Converts an energy in this unit to GeV
+    pub fn to_gev(self, energy: f64) -> f64 {
+        energy * self.gev_factor()
+    }
+}
 
 /// Supported input formats for gamma-ray data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataFormat {
     Csv,
     Json,
+    Ndjson,
     Ascii,
 }
 
+impl DataFormat {
+    /// Infers the format from the file extension, ignoring case
+    ///
+    /// `.csv` is CSV, `.json` is JSON, `.ndjson` or `.jsonl` are JSON Lines and `.txt`,
+    /// `.dat` or `.ascii` are ASCII tables.
+    /// A trailing `.gz` is skipped, so `events.csv.gz` is CSV.
+    pub fn from_path(path: &Path) -> Result<Self, DataError> {
+        let inner = match path.file_stem() {
+            Some(stem) if is_gzip(path) => Path::new(stem),
+            _ => path,
+        };
+        let extension = inner
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("csv") => Ok(DataFormat::Csv),
+            Some("json") => Ok(DataFormat::Json),
+            Some("ndjson") | Some("jsonl") => Ok(DataFormat::Ndjson),
+            Some("txt") | Some("dat") | Some("ascii") => Ok(DataFormat::Ascii),
+            _ => Err(DataError::UnknownFormat { path: path.to_path_buf() }),
+        }
+    }
+
+    /// Returns the default importer for this format
+    pub fn importer(self) -> Box<dyn DataImporter> {
+        match self {
+            DataFormat::Csv => Box::new(CsvImporter::new()),
+            DataFormat::Json => Box::new(JsonImporter::new()),
+            DataFormat::Ndjson => Box::new(NdjsonImporter::new()),
+            DataFormat::Ascii => Box::new(AsciiImporter::new()),
+        }
+    }
+}
+
 /// Trait for data importers
 pub trait DataImporter {
-    fn load_from_file(&self, path: &Path) -> Result<Vec<TimingData>, Box<dyn std::error::Error>>;
+    /// Reads measurements from any byte stream, such as stdin or an in-memory buffer
+    fn load_from_reader(&self, reader: &mut dyn Read) -> Result<Vec<TimingData>, DataError>;
+
+    /// Reads measurements from the file at `path`, decompressing `.gz` files
+    fn load_from_file(&self, path: &Path) -> Result<Vec<TimingData>, DataError> {
+        let mut reader = open_input(path)?;
+        self.load_from_reader(&mut reader)
+    }
+
+    /// Passes each measurement read from the stream to `callback` in order
+    ///
+    /// The default loads the whole stream first; row-oriented importers override it
+    /// so only one row is held in memory at a time.
+    fn stream_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        callback: &mut dyn FnMut(TimingData),
+    ) -> Result<(), DataError> {
+        for measurement in self.load_from_reader(reader)? {
+            callback(measurement);
+        }
+        Ok(())
+    }
+
+    /// Passes each measurement in the file at `path` to `callback` in order
+    fn stream(&self, path: &Path, callback: &mut dyn FnMut(TimingData)) -> Result<(), DataError> {
+        let mut reader = open_input(path)?;
+        self.stream_from_reader(&mut reader, callback)
+    }
+}
+
+/// Returns true if `path` ends in `.gz`, ignoring case
+fn is_gzip(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("gz"))
+}
+
+/// Opens `path` for buffered reading, wrapping `.gz` files in a gzip decoder
+fn open_input(path: &Path) -> Result<Box<dyn Read>, DataError> {
+    let file = File::open(path)?;
+    if !is_gzip(path) {
+        return Ok(Box::new(BufReader::new(file)));
+    }
+    
+    #[cfg(feature = "gzip")]
+    {
+        Ok(Box::new(BufReader::new(flate2::read::GzDecoder::new(file))))
+    }
+    #[cfg(not(feature = "gzip"))]
+    {
+        Err(DataError::Compressed { path: path.to_path_buf() })
+    }
+}
+
+/// Header names of the energy, arrival time and error columns in a CSV file, plus the
+/// optional weight, distance and redshift columns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnMap {
+    pub energy: String,
+    pub arrival_time: String,
+    pub error: String,
+    pub weight: Option<String>,
+    pub distance: Option<String>,
+    pub redshift: Option<String>,
+}
+
+impl ColumnMap {
+    /// Creates a mapping from the given header names
+    pub fn new(
+        energy: impl Into<String>,
+        arrival_time: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            energy: energy.into(),
+            arrival_time: arrival_time.into(),
+            error: error.into(),
+            weight: None,
+            distance: None,
+            redshift: None,
+        }
+    }
+
+    /// Also reads per-photon weights from the column with this header name
+    pub fn with_weight(mut self, weight: impl Into<String>) -> Self {
+        self.weight = Some(weight.into());
+        self
+    }
+
+    /// Also reads per-photon source distances in meters from the column with this header
+    pub fn with_distance(mut self, distance: impl Into<String>) -> Self {
+        self.distance = Some(distance.into());
+        self
+    }
+
+    /// Also reads per-photon source redshifts from the column with this header name
+    pub fn with_redshift(mut self, redshift: impl Into<String>) -> Self {
+        self.redshift = Some(redshift.into());
+        self
+    }
+
+    /// Resolves the mapped names to `[energy, arrival_time, error]` column indices and
+    /// the `[weight, distance, redshift]` column indices of those that are mapped
+    fn resolve(
+        &self,
+        headers: &csv::StringRecord,
+    ) -> Result<([usize; 3], [Option<usize>; 3]), DataError> {
+        let index_of = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim() == name)
+                .ok_or_else(|| DataError::MissingHeader { name: name.to_string() })
+        };
+        let columns = [index_of(&self.energy)?, index_of(&self.arrival_time)?, index_of(&self.error)?];
+        let optional = [&self.weight, &self.distance, &self.redshift]
+            .map(|name| name.as_deref().map(index_of).transpose());
+        let [weight, distance, redshift] = optional;
+        Ok((columns, [weight?, distance?, redshift?]))
+    }
 }
 
 /// CSV data importer
-pub struct CsvImporter;
+///
+/// Reads energy, arrival time and error from the first three columns, or from the
+/// header columns named by `column_map` when one is set. Per-photon weights are read
+/// from `weight_column` (or the mapped weight header) when configured; rows without
+/// that field are left unweighted. Source distances (in meters) and redshifts are read
+/// the same way from `distance_column` and `redshift_column`, staying `None` for rows
+/// without them. Under `ErrorPolicy::SkipAndReport` malformed rows are skipped rather
+/// than failing the load; `load_with_report` lists them.
+#[derive(Debug, Clone)]
+pub struct CsvImporter {
+    pub delimiter: u8,
+    pub has_headers: bool,
+    pub column_map: Option<ColumnMap>,
+    pub weight_column: Option<usize>,
+    pub distance_column: Option<usize>,
+    pub redshift_column: Option<usize>,
+    pub energy_unit: EnergyUnit,
+    pub on_error: ErrorPolicy,
+}
 
-impl DataImporter for CsvImporter {
-    fn load_from_file(&self, path: &Path) -> Result<Vec<TimingData>, Box<dyn std::error::Error>> {
-        let mut reader = Reader::from_path(path)?;
+impl CsvImporter {
+    /// Creates an importer for comma-separated files with a header row
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+            column_map: None,
+            weight_column: None,
+            distance_column: None,
+            redshift_column: None,
+            energy_unit: EnergyUnit::GeV,
+            on_error: ErrorPolicy::Abort,
+        }
+    }
+
+    /// Sets the field delimiter, e.g. `b'\t'` or `b';'`
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first row is a header rather than data
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Selects columns by header name; the file must then have a header row
+    pub fn with_column_map(mut self, column_map: ColumnMap) -> Self {
+        self.column_map = Some(column_map);
+        self
+    }
+
+    /// Reads per-photon weights from the column at `index`, such as 3 for a fourth column
+    ///
+    /// Ignored when a `column_map` is set; use `ColumnMap::with_weight` instead.
+    pub fn with_weight_column(mut self, index: usize) -> Self {
+        self.weight_column = Some(index);
+        self
+    }
+
+    /// Reads per-photon source distances in meters from the column at `index`
+    ///
+    /// Ignored when a `column_map` is set; use `ColumnMap::with_distance` instead.
+    pub fn with_distance_column(mut self, index: usize) -> Self {
+        self.distance_column = Some(index);
+        self
+    }
+
+    /// Reads per-photon source redshifts from the column at `index`
+    ///
+    /// Ignored when a `column_map` is set; use `ColumnMap::with_redshift` instead.
+    pub fn with_redshift_column(mut self, index: usize) -> Self {
+        self.redshift_column = Some(index);
+        self
+    }
+
+    /// Sets the unit of the energy column
+    pub fn with_energy_unit(mut self, energy_unit: EnergyUnit) -> Self {
+        self.energy_unit = energy_unit;
+        self
+    }
+
+    /// Sets how rows that cannot be read are handled
+    pub fn with_error_policy(mut self, on_error: ErrorPolicy) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Reads measurements from the stream along with a report of the skipped rows
+    ///
+    /// The report is empty under `ErrorPolicy::Abort`, where a bad row is an error.
+    /// I/O failures and unresolvable headers fail the load under either policy.
+    pub fn load_with_report(
+        &self,
+        reader: &mut dyn Read,
+    ) -> Result<(Vec<TimingData>, LoadReport), DataError> {
         let mut data = Vec::new();
+        let mut report = LoadReport::default();
+        self.read_records(reader, &mut |measurement| data.push(measurement), &mut report)?;
+        Ok((data, report))
+    }
+
+    /// Reads measurements from the file at `path` along with a report of the skipped rows
+    pub fn load_file_with_report(
+        &self,
+        path: &Path,
+    ) -> Result<(Vec<TimingData>, LoadReport), DataError> {
+        let mut reader = open_input(path)?;
+        self.load_with_report(&mut reader)
+    }
+
+    /// Streams rows to `callback`, recording skipped rows in `report`
+    fn read_records(
+        &self,
+        reader: &mut dyn Read,
+        callback: &mut dyn FnMut(TimingData),
+        report: &mut LoadReport,
+    ) -> Result<(), DataError> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .from_reader(reader);
+        let optional_columns = [self.weight_column, self.distance_column, self.redshift_column];
+        let ([energy_column, arrival_time_column, error_column], optional_columns) =
+            match &self.column_map {
+                Some(column_map) if self.has_headers => column_map.resolve(reader.headers()?)?,
+                Some(_) => {
+                    return Err(DataError::InvalidValue {
+                        line: 1,
+                        reason: "a column map selects columns by name and needs a header row"
+                            .to_string(),
+                    })
+                }
+                None => ([0, 1, 2], optional_columns),
+            };
+        let [weight_column, distance_column, redshift_column] = optional_columns;
         
         for result in reader.records() {
-            let record = result?;
-            let energy: f64 = record.get(0).unwrap_or("0").parse()?;
-            let arrival_time: f64 = record.get(1).unwrap_or("0").parse()?;
-            let error: f64 = record.get(2).unwrap_or("0").parse()?;
+            let parsed = result.map_err(DataError::from).and_then(|record| {
+                let line = record.position().map_or(0, |p| p.line() as usize);
+                let fields: Vec<&str> = record.iter().collect();
+                let energy = self.energy_unit.to_gev(parse_field(&fields, line, energy_column)?);
+                let arrival_time = parse_field(&fields, line, arrival_time_column)?;
+                let error = parse_field(&fields, line, error_column)?;
+                
+                let mut measurement = TimingData::new(energy, arrival_time, error);
+                measurement.weight = parse_optional_field(&fields, line, weight_column)?;
+                measurement.distance_m = parse_optional_field(&fields, line, distance_column)?;
+                measurement.redshift = parse_optional_field(&fields, line, redshift_column)?;
+                Ok(measurement)
+            });
+            match parsed {
+                Ok(measurement) => callback(measurement),
+                Err(error) if self.on_error == ErrorPolicy::SkipAndReport => {
+                    report.skipped.push(row_failure(error)?);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+impl Default for CsvImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataImporter for CsvImporter {
+    fn load_from_reader(&self, reader: &mut dyn Read) -> Result<Vec<TimingData>, DataError> {
+        let mut data = Vec::new();
+        self.stream_from_reader(reader, &mut |measurement| data.push(measurement))?;
+        Ok(data)
+    }
+
+    fn stream_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        callback: &mut dyn FnMut(TimingData),
+    ) -> Result<(), DataError> {
+        self.read_records(reader, callback, &mut LoadReport::default())
+    }
+}
+
+/// JSON data importer
+///
+/// Expects a top-level array of objects with `energy`, `arrival_time` and `error` fields
+/// and optional `weight`, `distance_m` and `redshift` fields, which are `None` when
+/// absent. Decoding errors from `load_from_reader` report the path as `-`.
+#[derive(Debug, Clone, Default)]
+pub struct JsonImporter {
+    pub energy_unit: EnergyUnit,
+}
+
+impl JsonImporter {
+    /// Creates an importer for energies in GeV
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the unit of the `energy` fields
+    pub fn with_energy_unit(mut self, energy_unit: EnergyUnit) -> Self {
+        self.energy_unit = energy_unit;
+        self
+    }
+}
+
+impl DataImporter for JsonImporter {
+    fn load_from_reader(&self, reader: &mut dyn Read) -> Result<Vec<TimingData>, DataError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        let mut data: Vec<TimingData> = serde_json::from_str(&contents).map_err(|source| {
+            DataError::Json { path: PathBuf::from("-"), source }
+        })?;
+        for measurement in &mut data {
+            measurement.energy = self.energy_unit.to_gev(measurement.energy);
+        }
+        
+        Ok(data)
+    }
+
+    fn load_from_file(&self, path: &Path) -> Result<Vec<TimingData>, DataError> {
+        let mut reader = open_input(path)?;
+        self.load_from_reader(&mut reader).map_err(|e| match e {
+            DataError::Json { source, .. } => DataError::Json { path: path.to_path_buf(), source },
+            other => other,
+        })
+    }
+}
+
+/// JSON Lines (NDJSON) importer
+///
+/// Expects one `TimingData` JSON object per line, with the optional fields of
+/// `JsonImporter`; blank lines are skipped. Rows are
+/// decoded one at a time, so `stream` never holds more than one line in memory.
+#[derive(Debug, Clone, Default)]
+pub struct NdjsonImporter {
+    pub energy_unit: EnergyUnit,
+}
+
+impl NdjsonImporter {
+    /// Creates an importer for energies in GeV
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the unit of the `energy` fields
+    pub fn with_energy_unit(mut self, energy_unit: EnergyUnit) -> Self {
+        self.energy_unit = energy_unit;
+        self
+    }
+}
+
+impl DataImporter for NdjsonImporter {
+    fn load_from_reader(&self, reader: &mut dyn Read) -> Result<Vec<TimingData>, DataError> {
+        let mut data = Vec::new();
+        self.stream_from_reader(reader, &mut |measurement| data.push(measurement))?;
+        Ok(data)
+    }
+
+    fn stream_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        callback: &mut dyn FnMut(TimingData),
+    ) -> Result<(), DataError> {
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let line_text = line?;
+            if line_text.trim().is_empty() {
+                continue;
+            }
             
-            data.push(TimingData { energy, arrival_time, error });
+            let line = index + 1;
+            let mut measurement: TimingData = serde_json::from_str(&line_text)
+                .map_err(|e| DataError::InvalidValue { line, reason: e.to_string() })?;
+            measurement.energy = self.energy_unit.to_gev(measurement.energy);
+            callback(measurement);
         }
         
+        Ok(())
+    }
+}
+
+/// Whitespace-delimited ASCII table importer
+///
+/// Blank lines and lines starting with `#` are skipped; extra columns are ignored
+/// unless one is selected with `with_weight_column`.
+#[derive(Debug, Clone)]
+pub struct AsciiImporter {
+    pub energy_column: usize,
+    pub arrival_time_column: usize,
+    pub error_column: usize,
+    pub weight_column: Option<usize>,
+    pub energy_unit: EnergyUnit,
+}
+
+impl AsciiImporter {
+    /// Creates an importer reading energy, arrival time and error from columns 0, 1 and 2
+    pub fn new() -> Self {
+        Self {
+            energy_column: 0,
+            arrival_time_column: 1,
+            error_column: 2,
+            weight_column: None,
+            energy_unit: EnergyUnit::GeV,
+        }
+    }
+
+    /// Sets the unit of the energy column
+    pub fn with_energy_unit(mut self, energy_unit: EnergyUnit) -> Self {
+        self.energy_unit = energy_unit;
+        self
+    }
+
+    /// Sets which column indices map to energy, arrival time and error
+    pub fn with_columns(mut self, energy: usize, arrival_time: usize, error: usize) -> Self {
+        self.energy_column = energy;
+        self.arrival_time_column = arrival_time;
+        self.error_column = error;
+        self
+    }
+
+    /// Reads per-photon weights from the column at `index`; rows without it are unweighted
+    pub fn with_weight_column(mut self, index: usize) -> Self {
+        self.weight_column = Some(index);
+        self
+    }
+}
+
+impl Default for AsciiImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataImporter for AsciiImporter {
+    fn load_from_reader(&self, reader: &mut dyn Read) -> Result<Vec<TimingData>, DataError> {
+        let mut data = Vec::new();
+        self.stream_from_reader(reader, &mut |measurement| data.push(measurement))?;
         Ok(data)
     }
+
+    fn stream_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        callback: &mut dyn FnMut(TimingData),
+    ) -> Result<(), DataError> {
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let line_text = line?;
+            let trimmed = line_text.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            
+            let line = index + 1;
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+            let energy = self.energy_unit.to_gev(parse_field(&fields, line, self.energy_column)?);
+            let arrival_time = parse_field(&fields, line, self.arrival_time_column)?;
+            let error = parse_field(&fields, line, self.error_column)?;
+            
+            let mut measurement = TimingData::new(energy, arrival_time, error);
+            measurement.weight = parse_optional_field(&fields, line, self.weight_column)?;
+            callback(measurement);
+        }
+        
+        Ok(())
+    }
 }
 
 /// Trait for data exporters
@@ -41,6 +721,56 @@ pub trait DataExporter {
     fn save_to_file(&self, data: &[TimingData], path: &Path) -> Result<(), Box<dyn std::error::Error>>;
 }
 
+/// CSV data exporter
+///
+/// Writes an `energy,arrival_time,error` header followed by one row per measurement,
+/// the layout `CsvImporter` reads back. If any measurement is weighted a `weight`
+/// column is added, and `distance_m` and `redshift` columns follow when any
+/// measurement carries that geometry; measurements without it leave the field empty.
+/// Read the extra columns back with a `ColumnMap` naming them, or by index, e.g.
+/// `CsvImporter::with_weight_column(3)`.
+pub struct CsvExporter;
+
+impl DataExporter for CsvExporter {
+    fn save_to_file(&self, data: &[TimingData], path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let weighted = data.iter().any(|m| m.weight.is_some());
+        let has_distance = data.iter().any(|m| m.distance_m.is_some());
+        let has_redshift = data.iter().any(|m| m.redshift.is_some());
+        let optional = |value: Option<f64>| value.map_or_else(String::new, |v| v.to_string());
+        let mut writer = csv::Writer::from_path(path)?;
+        let mut header = vec!["energy", "arrival_time", "error"];
+        if weighted {
+            header.push("weight");
+        }
+        if has_distance {
+            header.push("distance_m");
+        }
+        if has_redshift {
+            header.push("redshift");
+        }
+        writer.write_record(&header)?;
+        for measurement in data {
+            let mut record = vec![
+                measurement.energy.to_string(),
+                measurement.arrival_time.to_string(),
+                measurement.error.to_string(),
+            ];
+            if weighted {
+                record.push(measurement.weight_factor().to_string());
+            }
+            if has_distance {
+                record.push(optional(measurement.distance_m));
+            }
+            if has_redshift {
+                record.push(optional(measurement.redshift));
+            }
+            writer.write_record(&record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
 /// JSON data exporter
 pub struct JsonExporter;
 
@@ -50,4 +780,115 @@ impl DataExporter for JsonExporter {
         std::fs::write(path, json_data)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Exporter for the anomalies returned by `GammaRayAnalyzer::detect_anomalies`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyExporter {
+    /// `energy,measured_time,expected_time,deviation,significance` header and one row
+    /// per anomaly
+    Csv,
+    /// Pretty-printed JSON array of the full anomaly records
+    Json,
+}
+
+impl AnomalyExporter {
+    /// JSON for a `.json` extension (case-insensitive), CSV otherwise
+    pub fn for_path(path: &Path) -> Self {
+        match DataFormat::from_path(path) {
+            Ok(DataFormat::Json) => AnomalyExporter::Json,
+            _ => AnomalyExporter::Csv,
+        }
+    }
+
+    /// Writes the anomalies to `path`
+    pub fn save_to_file(
+        &self,
+        anomalies: &[AnomalyDetectionResult],
+        path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match self {
+            AnomalyExporter::Csv => {
+                let mut writer = csv::Writer::from_path(path)?;
+                writer.write_record([
+                    "energy",
+                    "measured_time",
+                    "expected_time",
+                    "deviation",
+                    "significance",
+                ])?;
+                for anomaly in anomalies {
+                    writer.write_record([
+                        anomaly.energy.to_string(),
+                        anomaly.measured_time.to_string(),
+                        anomaly.expected_time.to_string(),
+                        anomaly.deviation.to_string(),
+                        anomaly.significance.to_string(),
+                    ])?;
+                }
+                writer.flush()?;
+            }
+            AnomalyExporter::Json => {
+                let json_data = serde_json::to_string_pretty(anomalies)?;
+                std::fs::write(path, json_data)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Name recorded in the `tool` field of exported results
+const TOOL_NAME: &str = "lightspeedvalidator";
+
+/// Document written by `ResultExporter`: an envelope naming the tool, its version and
+/// the analysis configuration (`null` when not recorded) around the test result,
+/// followed by the detected anomalies and, optionally, the input measurements
+#[derive(Serialize)]
+struct ResultDocument<'a> {
+    tool: &'static str,
+    version: &'static str,
+    config: Option<&'a AnalysisConfig>,
+    result: &'a LightSpeedTestResult,
+    anomalies: &'a [AnomalyDetectionResult],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    measurements: Option<&'a [TimingData]>,
+}
+
+/// JSON exporter for analysis results
+#[derive(Debug, Clone, Default)]
+pub struct ResultExporter {
+    /// Also write the analyzed measurements under a `measurements` key
+    pub include_measurements: bool,
+    /// Also write this analysis configuration under a `config` key
+    pub config: Option<AnalysisConfig>,
+}
+
+impl ResultExporter {
+    /// Creates an exporter that writes only the analysis result
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the test result and detected anomalies as pretty-printed JSON
+    ///
+    /// The version is the crate version that wrote the file.
+    pub fn save_to_file(
+        &self,
+        result: &LightSpeedTestResult,
+        anomalies: &[AnomalyDetectionResult],
+        measurements: &[TimingData],
+        path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let document = ResultDocument {
+            tool: TOOL_NAME,
+            version: env!("CARGO_PKG_VERSION"),
+            config: self.config.as_ref(),
+            result,
+            anomalies,
+            measurements: self.include_measurements.then_some(measurements),
+        };
+        let json_data = serde_json::to_string_pretty(&document)?;
+        std::fs::write(path, json_data)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+// src/fits.rs
+use std::fs::{File, OpenOptions};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fitsio::tables::{ColumnDataType, ColumnDescription};
+use fitsio::FitsFile;
+
+use crate::analyzer::TimingData;
+use crate::data::{DataError, DataExporter, DataImporter, EnergyUnit};
+
+/// Fermi mission elapsed time epoch (2001-01-01T00:00:00 UTC) in Unix seconds
+pub const FERMI_MET_EPOCH_UNIX: f64 = 978_307_200.0;
+
+/// FITS binary-table importer for event lists such as Fermi-LAT `EVENTS` extensions
+///
+/// Energy, time and error are read from the named columns of the named HDU.
+/// `time_offset` is added to every time, e.g. `FERMI_MET_EPOCH_UNIX` to turn MET
+/// into Unix-epoch seconds. Energies default to MeV, the unit of Fermi-LAT event files.
+#[derive(Debug, Clone)]
+pub struct FitsImporter {
+    pub hdu: String,
+    pub energy_column: String,
+    pub time_column: String,
+    pub error_column: String,
+    pub time_offset: f64,
+    pub energy_unit: EnergyUnit,
+}
+
+impl FitsImporter {
+    /// Creates an importer reading `ENERGY`, `TIME` and `TIME_ERR` from the `EVENTS` HDU
+    pub fn new() -> Self {
+        Self {
+            hdu: "EVENTS".to_string(),
+            energy_column: "ENERGY".to_string(),
+            time_column: "TIME".to_string(),
+            error_column: "TIME_ERR".to_string(),
+            time_offset: 0.0,
+            energy_unit: EnergyUnit::MeV,
+        }
+    }
+
+    /// Sets the name of the binary-table HDU to read
+    pub fn with_hdu(mut self, hdu: impl Into<String>) -> Self {
+        self.hdu = hdu.into();
+        self
+    }
+
+    /// Sets the energy, time and error column names
+    pub fn with_columns(
+        mut self,
+        energy: impl Into<String>,
+        time: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        self.energy_column = energy.into();
+        self.time_column = time.into();
+        self.error_column = error.into();
+        self
+    }
+
+    /// Sets the offset in seconds added to every time, such as a MET epoch
+    pub fn with_time_offset(mut self, time_offset: f64) -> Self {
+        self.time_offset = time_offset;
+        self
+    }
+
+    /// Sets the unit of the energy column
+    pub fn with_energy_unit(mut self, energy_unit: EnergyUnit) -> Self {
+        self.energy_unit = energy_unit;
+        self
+    }
+}
+
+impl Default for FitsImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataImporter for FitsImporter {
+    /// CFITSIO reads from files, so the stream is spooled to a temporary file first
+    fn load_from_reader(&self, reader: &mut dyn Read) -> Result<Vec<TimingData>, DataError> {
+        let (spool, mut file) = SpoolFile::create()?;
+        std::io::copy(reader, &mut file)?;
+        drop(file);
+        
+        self.load_from_file(&spool.path)
+    }
+
+    fn load_from_file(&self, path: &Path) -> Result<Vec<TimingData>, DataError> {
+        let mut file = FitsFile::open(path)?;
+        let hdu = file.hdu(self.hdu.as_str())?;
+        let energies: Vec<f64> = hdu.read_col(&mut file, &self.energy_column)?;
+        let times: Vec<f64> = hdu.read_col(&mut file, &self.time_column)?;
+        let errors: Vec<f64> = hdu.read_col(&mut file, &self.error_column)?;
+        
+        let data = energies
+            .into_iter()
+            .zip(times)
+            .zip(errors)
+            .map(|((energy, time), error)| {
+                TimingData::new(self.energy_unit.to_gev(energy), time + self.time_offset, error)
+            })
+            .collect();
+        
+        Ok(data)
+    }
+}
+
+/// FITS binary-table exporter writing an event list
+///
+/// Writes `ENERGY` (GeV), `TIME` (s) and `ERROR` (s) columns with matching `TUNITn`
+/// keywords to a binary-table HDU named `hdu`, replacing any existing file. Read it
+/// back with `FitsImporter::new().with_hdu(..).with_columns("ENERGY", "TIME", "ERROR")`
+/// and `with_energy_unit(EnergyUnit::GeV)`.
+#[derive(Debug, Clone)]
+pub struct FitsExporter {
+    pub hdu: String,
+}
+
+impl FitsExporter {
+    /// Creates an exporter writing to an `EVENTS` HDU
+    pub fn new() -> Self {
+        Self { hdu: "EVENTS".to_string() }
+    }
+
+    /// Sets the name of the binary-table HDU to write
+    pub fn with_hdu(mut self, hdu: impl Into<String>) -> Self {
+        self.hdu = hdu.into();
+        self
+    }
+}
+
+impl Default for FitsExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataExporter for FitsExporter {
+    fn save_to_file(&self, data: &[TimingData], path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let columns = [("ENERGY", "GeV"), ("TIME", "s"), ("ERROR", "s")];
+        let descriptions = columns
+            .iter()
+            .map(|(name, _)| {
+                ColumnDescription::new(*name).with_type(ColumnDataType::Double).create()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        let mut file = FitsFile::create(path).overwrite().open()?;
+        let hdu = file.create_table(self.hdu.clone(), &descriptions)?;
+        let energies: Vec<f64> = data.iter().map(|m| m.energy).collect();
+        let times: Vec<f64> = data.iter().map(|m| m.arrival_time).collect();
+        let errors: Vec<f64> = data.iter().map(|m| m.error).collect();
+        hdu.write_col(&mut file, "ENERGY", &energies)?;
+        hdu.write_col(&mut file, "TIME", &times)?;
+        hdu.write_col(&mut file, "ERROR", &errors)?;
+        for (i, (_, unit)) in columns.iter().enumerate() {
+            hdu.write_key(&mut file, &format!("TUNIT{}", i + 1), *unit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Temporary file that is removed when dropped
+struct SpoolFile {
+    path: PathBuf,
+}
+
+impl SpoolFile {
+    /// Creates a new, previously nonexistent file in the temporary directory
+    ///
+    /// Names combine the process id, a per-process counter and the clock, and the file
+    /// is opened with `create_new`, so concurrent loads never share or clobber a file
+    /// and an existing path planted by another user is never followed.
+    fn create() -> std::io::Result<(Self, File)> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        loop {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.subsec_nanos());
+            let name = format!(
+                "lsv_stdin_{}_{}_{}.fits",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed),
+                nanos
+            );
+            let path = std::env::temp_dir().join(name);
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Self { path }, file)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for SpoolFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
@@ -3,9 +3,39 @@
 
 pub mod analyzer;
 pub mod cli;
+pub mod cosmology;
 pub mod data;
+#[cfg(feature = "fits")]
+pub mod fits;
+pub mod report;
+pub mod simulation;
 pub mod statistics;
+pub mod stats_math;
 
 // Re-export key types and functions for easy access
-pub use analyzer::{GammaRayAnalyzer, TimingData};
-pub use statistics::{LightSpeedTestResult, SpeedOfLightHypothesisTest};
\ No newline at end of file
+pub use analyzer::{
+    log_bin_edges, AnalysisConfig, AnalysisError, AnomalyDetectionResult, AnomalyKind,
+    BandSummary, CrossCorrelationResult, DisCanResult, DispersionOrder, GammaRayAnalyzer,
+    JackknifeResult, LightSpeedTestResult, LivFitResult, LossKind, MergeError, MixtureResult,
+    ProgressCallback, QuantumGravityModel, RegressionResult, SourceGeometry, SpectralLag,
+    SpectralLagBin, TimeReference, TimingData, TimingDataError,
+};
+pub use cli::{
+    AnalyzeArgs, Cli, CliError, CliExecutor, Commands, CompareArgs, InputFormat, ReportArgs,
+    SimulateArgs, ValidationSummary,
+};
+pub use cosmology::{Cosmology, DistanceTable};
+pub use data::{
+    AnomalyExporter, AsciiImporter, ColumnMap, CsvExporter, CsvImporter, DataError, DataExporter,
+    DataFormat, DataImporter, EnergyUnit, ErrorPolicy, JsonExporter, JsonImporter, LoadReport,
+    NdjsonImporter, ResultExporter,
+};
+#[cfg(feature = "fits")]
+pub use fits::{FitsExporter, FitsImporter};
+pub use report::AnalysisReport;
+pub use simulation::{simulate, PowerLawSpectrum, SimulationConfig};
+pub use statistics::{
+    chi_squared_pvalue, BayesianResult, ChiSquaredResult, CiMethod, CombinedLimit,
+    ConfidenceInterval, FTestResult, HypothesisTestResult, IcResult, KsResult, LrtResult,
+    ModelComparison, MwuResult, ShapiroResult, SpeedOfLightHypothesisTest, ZTestResult,
+};
\ No newline at end of file
@@ -0,0 +1,96 @@
+// src/report.rs
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::analyzer::{AnomalyDetectionResult, GammaRayAnalyzer, LightSpeedTestResult, LivFitResult};
+
+/// Human-readable summary of a full analysis run
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisReport {
+    pub measurement_count: usize,
+    pub result: LightSpeedTestResult,
+    pub liv_fit: LivFitResult,
+    pub energy_scale_lower_bound: Option<f64>, // 95% lower limit on E_QG in GeV
+    pub anomalies: Vec<AnomalyDetectionResult>,
+}
+
+impl AnalysisReport {
+    /// Runs the constancy test, LIV slope fit and anomaly detection on `analyzer`
+    ///
+    /// `travel_time` is the source light-travel time in seconds used for the E_QG bound.
+    pub fn from_analyzer(analyzer: &GammaRayAnalyzer, travel_time: Option<f64>) -> Self {
+        let liv_fit = analyzer.fit_liv_slope();
+        let energy_scale_lower_bound =
+            travel_time.and_then(|t| liv_fit.energy_scale_lower_bound(t, 0.95));
+        
+        Self {
+            measurement_count: analyzer.len(),
+            result: analyzer.test_light_speed_constancy(),
+            liv_fit,
+            energy_scale_lower_bound,
+            anomalies: analyzer.detect_anomalies(),
+        }
+    }
+
+    /// Renders the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let verdict = if self.result.insufficient_data {
+            "untested (fewer than two measurements)"
+        } else if self.result.is_valid {
+            "consistent with constant c"
+        } else {
+            "inconsistent with constant c"
+        };
+        
+        // Writing to a String cannot fail
+        let _ = writeln!(out, "# Light Speed Constancy Report\n");
+        let _ = writeln!(out, "Measurements analyzed: {}\n", self.measurement_count);
+        
+        let _ = writeln!(out, "## Constancy test\n");
+        let _ = writeln!(out, "| Quantity | Value |");
+        let _ = writeln!(out, "|---|---|");
+        let _ = writeln!(out, "| Chi-squared | {:.6} |", self.result.chi_squared);
+        let _ = writeln!(out, "| Degrees of freedom | {} |", self.result.degrees_of_freedom);
+        let _ = writeln!(out, "| Reduced chi-squared | {:.6} |", self.result.reduced_chi_squared);
+        let _ = writeln!(out, "| P-value | {:.6e} |", self.result.p_value);
+        let _ = writeln!(out, "| Confidence level | {:.6} |", self.result.confidence_level);
+        let _ = writeln!(out, "| Deviation estimate (s) | {} |", format_option(self.result.deviation_estimate));
+        let _ = writeln!(out, "| Verdict | {} |\n", verdict);
+        
+        let _ = writeln!(out, "## LIV slope fit\n");
+        let _ = writeln!(out, "| Quantity | Value |");
+        let _ = writeln!(out, "|---|---|");
+        let _ = writeln!(out, "| Slope (s/GeV) | {:.6e} |", self.liv_fit.slope);
+        let _ = writeln!(out, "| Slope error (s/GeV) | {:.6e} |", self.liv_fit.slope_error);
+        let _ = writeln!(out, "| Reduced chi-squared | {:.6} |", self.liv_fit.reduced_chi_squared);
+        let _ = writeln!(out, "| E_QG lower bound (GeV, 95%) | {} |\n", format_option(self.energy_scale_lower_bound));
+        
+        let _ = writeln!(out, "## Anomalies\n");
+        if self.anomalies.is_empty() {
+            let _ = writeln!(out, "No anomalies detected.");
+        } else {
+            let _ = writeln!(out, "| Energy (GeV) | Measured (s) | Expected (s) | Deviation (s) | Significance (σ) |");
+            let _ = writeln!(out, "|---|---|---|---|---|");
+            for anomaly in &self.anomalies {
+                let _ = writeln!(
+                    out,
+                    "| {:.6} | {:.6} | {:.6} | {:.6e} | {:.2} |",
+                    anomaly.energy,
+                    anomaly.measured_time,
+                    anomaly.expected_time,
+                    anomaly.deviation,
+                    anomaly.significance
+                );
+            }
+        }
+        
+        out
+    }
+}
+
+/// Formats an optional value in scientific notation, or `n/a`
+fn format_option(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |v| format!("{:.6e}", v))
+}
@@ -0,0 +1,136 @@
+// src/simulation.rs
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::analyzer::{DispersionOrder, QuantumGravityModel, TimingData, SPEED_OF_LIGHT};
+use crate::cosmology::Cosmology;
+
+/// Parameters for generating a synthetic gamma-ray dataset with an injected LIV signal
+///
+/// Simulated arrival times are relative to the arrival of an infinitely soft photon
+/// emitted at the same instant, so they contain only the dispersion delay and noise.
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub photon_count: usize,
+    pub energy_min: f64,              // Lower energy bound in GeV
+    pub energy_max: f64,              // Upper energy bound in GeV
+    pub spectral_index: f64,          // Photon index Γ of dN/dE ∝ E^-Γ
+    pub distance_m: Option<f64>,      // Source distance in meters
+    pub redshift: Option<f64>,        // Source redshift, used when no distance is given
+    pub energy_scale: f64,            // Injected E_QG in GeV
+    pub dispersion_order: DispersionOrder,
+    pub noise_sigma: f64,             // Gaussian timing noise in seconds
+    pub seed: Option<u64>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            photon_count: 1000,
+            energy_min: 0.1,
+            energy_max: 100.0,
+            spectral_index: 2.0,
+            distance_m: None,
+            redshift: Some(1.0),
+            energy_scale: 1e19,
+            dispersion_order: DispersionOrder::Linear,
+            noise_sigma: 0.01,
+            seed: None,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Light-travel time to the source, from the distance or the Planck 2018 redshift distance
+    pub fn travel_time(&self) -> Option<f64> {
+        self.distance_m
+            .or_else(|| self.redshift.map(|z| Cosmology::planck_2018().comoving_distance(z)))
+            .map(|distance| distance / SPEED_OF_LIGHT)
+    }
+
+    /// Photon energy spectrum between the configured bounds
+    pub fn spectrum(&self) -> PowerLawSpectrum {
+        PowerLawSpectrum {
+            index: self.spectral_index,
+            e_min: self.energy_min,
+            e_max: self.energy_max,
+        }
+    }
+
+    /// Injected quantum gravity model
+    pub fn model(&self) -> QuantumGravityModel {
+        QuantumGravityModel {
+            planck_length: 1.616e-35,
+            quantum_gravity_effect: 0.0,
+            energy_scale: self.energy_scale,
+            dispersion_order: self.dispersion_order,
+        }
+    }
+}
+
+/// Power-law photon spectrum dN/dE ∝ E^-index between `e_min` and `e_max` in GeV
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerLawSpectrum {
+    pub index: f64,
+    pub e_min: f64,
+    pub e_max: f64,
+}
+
+impl PowerLawSpectrum {
+    /// Draws one energy by inverse-transform sampling of the cumulative spectrum
+    ///
+    /// An index of 1 is sampled uniformly in log-energy. The bounds are assumed to
+    /// satisfy `0 < e_min < e_max`.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.gen();
+        if (self.index - 1.0).abs() < 1e-12 {
+            return self.e_min * (self.e_max / self.e_min).powf(u);
+        }
+        let k = 1.0 - self.index;
+        let low = self.e_min.powf(k);
+        let high = self.e_max.powf(k);
+        (low + u * (high - low)).powf(1.0 / k)
+    }
+}
+
+/// Generates a synthetic dataset according to `config`
+///
+/// Returns an error if neither a distance nor a redshift is configured or the
+/// energy range is empty.
+pub fn simulate(config: &SimulationConfig) -> Result<Vec<TimingData>, String> {
+    let travel_time = config
+        .travel_time()
+        .ok_or_else(|| "simulation requires a source distance or redshift".to_string())?;
+    if !(config.energy_min > 0.0 && config.energy_max > config.energy_min) {
+        return Err(format!(
+            "invalid energy range [{}, {}] GeV",
+            config.energy_min, config.energy_max
+        ));
+    }
+    
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let model = config.model();
+    let spectrum = config.spectrum();
+    
+    let data = (0..config.photon_count)
+        .map(|_| {
+            let energy = spectrum.sample(&mut rng);
+            let delay = model.time_delay(energy, Some(travel_time));
+            let noise = config.noise_sigma * standard_normal(&mut rng);
+            TimingData::new(energy, delay + noise, config.noise_sigma)
+        })
+        .collect();
+    
+    Ok(data)
+}
+
+/// Standard normal deviate via the Box-Muller transform
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    // gen() is in [0, 1); shift to (0, 1] so the logarithm stays finite
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
@@ -1,82 +1,874 @@
 // src/statistics.rs
-use crate::analyzer::{TimingData, LightSpeedTestResult, ChiSquaredResult, BayesianResult, 
-                     ModelComparison, ConfidenceInterval, HypothesisTestResult};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use crate::analyzer::{TimingData, SPEED_OF_LIGHT};
+use crate::cosmology::Cosmology;
+use crate::stats_math::{
+    normal_cdf, regularized_beta, regularized_gamma_q, stable_sum, CompensatedSum,
+};
+
+/// Default number of bootstrap resamples
+pub const DEFAULT_BOOTSTRAP_RESAMPLES: usize = 1000;
+/// Seed used when no bootstrap seed is supplied, keeping results reproducible
+pub const DEFAULT_BOOTSTRAP_SEED: u64 = 0x5eed_1157;
+
+/// Default number of grid points for the Bayesian posterior
+pub const DEFAULT_GRID_POINTS: usize = 2001;
+
+/// Survival function of the chi-squared distribution
+///
+/// Returns the probability of observing a statistic at least as large as `chi_sq`
+/// with `dof` degrees of freedom, i.e. `Q(dof/2, chi_sq/2)`.
+pub fn chi_squared_pvalue(chi_sq: f64, dof: usize) -> f64 {
+    if chi_sq.is_nan() {
+        return f64::NAN;
+    }
+    if dof == 0 || chi_sq <= 0.0 {
+        return 1.0;
+    }
+    regularized_gamma_q(dof as f64 / 2.0, chi_sq / 2.0)
+}
+
+/// Survival function of the Kolmogorov distribution, `Q_KS(λ)`
+fn kolmogorov_survival(lambda: f64) -> f64 {
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for k in 1..=100 {
+        let kf = k as f64;
+        let term = sign * (-2.0 * kf * kf * lambda * lambda).exp();
+        sum += term;
+        if term.abs() < 1e-12 * sum.abs() {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Cumulative distribution function of Student's t distribution with `dof` degrees of freedom
+fn student_t_cdf(t: f64, dof: f64) -> f64 {
+    let tail = 0.5 * regularized_beta(dof / 2.0, 0.5, dof / (dof + t * t));
+    if t > 0.0 { 1.0 - tail } else { tail }
+}
+
+/// Cumulative distribution function of the F distribution with `(d1, d2)` degrees of freedom
+fn f_distribution_cdf(f: f64, d1: f64, d2: f64) -> f64 {
+    if f <= 0.0 {
+        return 0.0;
+    }
+    regularized_beta(d1 / 2.0, d2 / 2.0, d1 * f / (d1 * f + d2))
+}
+
+/// Quantile of the standard normal distribution for `0.5 < p < 1`
+pub(crate) fn standard_normal_quantile(p: f64) -> f64 {
+    upper_quantile(normal_cdf, p)
+}
+
+/// Quantile of Student's t distribution with `dof` degrees of freedom for `0.5 < p < 1`
+pub(crate) fn student_t_quantile(p: f64, dof: f64) -> f64 {
+    upper_quantile(|t| student_t_cdf(t, dof), p)
+}
+
+/// Quantile of the standard normal distribution for `0 < p < 1`
+fn normal_quantile(p: f64) -> f64 {
+    if p > 0.5 {
+        standard_normal_quantile(p)
+    } else if p < 0.5 {
+        -standard_normal_quantile(1.0 - p)
+    } else {
+        0.0
+    }
+}
+
+/// Evaluates the polynomial with coefficients `coefficients` (constant term first) at `x`
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+}
+
+/// Inverts an increasing CDF by bisection, returning `x` with `cdf(x) = p` for `p > 0.5`
+fn upper_quantile(cdf: impl Fn(f64) -> f64, p: f64) -> f64 {
+    let mut low = 0.0;
+    let mut high = 1.0;
+    while cdf(high) < p {
+        low = high;
+        high *= 2.0;
+    }
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if cdf(mid) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Returns true if a measurement's error can be used as an inverse-variance weight
+fn has_usable_error(measurement: &TimingData) -> bool {
+    let error = measurement.weighted_error();
+    error.is_finite() && error > 0.0
+}
+
+/// Inverse-variance weighted mean arrival time, skipping measurements without a usable error
+fn weighted_mean<'a, I>(measurements: I) -> Option<f64>
+where
+    I: IntoIterator<Item = &'a TimingData>,
+{
+    let mut weighted_sum = CompensatedSum::default();
+    let mut total_weight = CompensatedSum::default();
+    for m in measurements.into_iter().filter(|m| has_usable_error(m)) {
+        let weight = m.weight_factor() / (m.error * m.error);
+        weighted_sum.add(weight * m.arrival_time);
+        total_weight.add(weight);
+    }
+    let total_weight = total_weight.total();
+    (total_weight > 0.0).then(|| weighted_sum.total() / total_weight)
+}
+
+/// Weighted least-squares straight line `arrival_time = intercept + slope · energy`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LinearFit {
+    pub slope: f64,
+    pub slope_variance: f64,
+    pub intercept: f64,
+    pub intercept_variance: f64,
+    pub covariance: f64,
+    pub r_squared: f64,
+    pub chi_squared: f64,
+    pub points: usize,
+}
+
+/// Fits arrival time against energy with inverse-variance weights
+///
+/// Returns `None` with fewer than two usable points or no spread in energy.
+pub(crate) fn weighted_linear_fit<'a, I>(measurements: I) -> Option<LinearFit>
+where
+    I: IntoIterator<Item = &'a TimingData>,
+{
+    let usable: Vec<&TimingData> = measurements.into_iter().filter(|m| has_usable_error(m)).collect();
+    if usable.len() < 2 {
+        return None;
+    }
+    
+    let (mut s, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for m in &usable {
+        let weight = m.weight_factor() / (m.error * m.error);
+        s += weight;
+        sx += weight * m.energy;
+        sy += weight * m.arrival_time;
+        sxx += weight * m.energy * m.energy;
+        sxy += weight * m.energy * m.arrival_time;
+    }
+    let delta = s * sxx - sx * sx;
+    if delta <= 0.0 || delta <= 1e-12 * s * sxx {
+        return None;
+    }
+    
+    let slope = (s * sxy - sx * sy) / delta;
+    let intercept = (sxx * sy - sx * sxy) / delta;
+    let chi_squared = stable_sum(usable.iter().map(|m| {
+        let residual = (m.arrival_time - intercept - slope * m.energy) / m.weighted_error();
+        residual * residual
+    }));
+    
+    // Weighted total sum of squares about the weighted mean arrival time
+    let mean = sy / s;
+    let total_squares = stable_sum(usable.iter().map(|m| {
+        let deviation = (m.arrival_time - mean) / m.weighted_error();
+        deviation * deviation
+    }));
+    let r_squared = if total_squares > 0.0 { 1.0 - chi_squared / total_squares } else { 1.0 };
+    
+    // Var(slope) = 2 / (∂²χ²/∂slope²) with the intercept profiled out
+    Some(LinearFit {
+        slope,
+        slope_variance: s / delta,
+        intercept,
+        intercept_variance: sxx / delta,
+        covariance: -sx / delta,
+        r_squared,
+        chi_squared,
+        points: usable.len(),
+    })
+}
+
+/// Gaussian log-likelihood of `chi_squared` given the measurement errors
+fn gaussian_log_likelihood(measurements: &[&TimingData], chi_squared: f64) -> f64 {
+    let normalization: f64 = measurements
+        .iter()
+        .map(|m| m.weighted_error().ln() + 0.5 * (2.0 * std::f64::consts::PI).ln())
+        .sum();
+    -0.5 * chi_squared - normalization
+}
+
+/// Linearly interpolated percentile (`q` in [0, 1]) of an ascending slice
+pub(crate) fn percentile_sorted(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+/// Weighted median of `(value, weight)` pairs, reordering them by value
+///
+/// Returns the smallest value whose cumulative weight reaches half the total, or the
+/// midpoint of two values when the halfway point falls exactly between them. Pairs
+/// with a non-positive or non-finite weight are ignored; `None` if none remain.
+pub(crate) fn weighted_median(values: &mut Vec<(f64, f64)>) -> Option<f64> {
+    values.retain(|(_, weight)| *weight > 0.0 && weight.is_finite());
+    values.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let half = values.iter().map(|(_, weight)| weight).sum::<f64>() / 2.0;
+    let mut cumulative = 0.0;
+    for (i, (value, weight)) in values.iter().enumerate() {
+        cumulative += weight;
+        if cumulative == half {
+            return Some(values.get(i + 1).map_or(*value, |next| 0.5 * (value + next.0)));
+        }
+        if cumulative > half {
+            return Some(*value);
+        }
+    }
+    None
+}
 
 /// Statistical tests for light speed constancy
 pub struct SpeedOfLightHypothesisTest;
 
 impl SpeedOfLightHypothesisTest {
     /// Performs chi-squared test for light speed variation
+    ///
+    /// The constant-c reference model predicts that every photon arrives at the same
+    /// time, estimated as the inverse-variance weighted mean arrival time. Measurements
+    /// with a non-positive or non-finite error carry no weight and are skipped.
     pub fn chi_squared_test(measurements: &[TimingData]) -> ChiSquaredResult {
-        // For simplicity, we'll return a mock result
-        // In a real implementation, this would:
-        // 1. Calculate expected arrival times based on light speed assumption
-        // 2. Compute residuals (measured - expected)
-        // 3. Calculate chi-squared statistic
-        // 4. Determine degrees of freedom
-        // 5. Calculate p-value
+        let usable: Vec<&TimingData> = measurements.iter().filter(|m| has_usable_error(m)).collect();
+        
+        let degrees_of_freedom = usable.len().saturating_sub(1);
+        if degrees_of_freedom == 0 {
+            return ChiSquaredResult {
+                chi_squared: 0.0,
+                degrees_of_freedom,
+                p_value: 1.0,
+                is_significant: false,
+            };
+        }
+        
+        let reference_time = weighted_mean(usable.iter().copied()).unwrap_or(0.0);
+        
+        let chi_squared = stable_sum(usable.iter().map(|m| {
+            let residual = (m.arrival_time - reference_time) / m.weighted_error();
+            residual * residual
+        }));
+        let p_value = chi_squared_pvalue(chi_squared, degrees_of_freedom);
         
         ChiSquaredResult {
-            chi_squared: 0.0,
-            degrees_of_freedom: measurements.len().saturating_sub(1),
-            p_value: 1.0,
-            is_significant: false,
+            chi_squared,
+            degrees_of_freedom,
+            p_value,
+            is_significant: p_value < 0.05,
+        }
+    }
+
+    /// Kolmogorov-Smirnov test of the constant-c residuals against a standard normal
+    ///
+    /// Residuals from the weighted mean arrival time are divided by their errors and
+    /// compared with the standard normal CDF. The p-value uses the asymptotic
+    /// Kolmogorov distribution with the Stephens small-sample correction.
+    pub fn ks_normality_test(measurements: &[TimingData]) -> KsResult {
+        let usable: Vec<&TimingData> = measurements.iter().filter(|m| has_usable_error(m)).collect();
+        let reference_time = match weighted_mean(usable.iter().copied()) {
+            Some(reference_time) => reference_time,
+            None => return KsResult { statistic: 0.0, p_value: 1.0, is_normal: true },
+        };
+        
+        let mut standardized: Vec<f64> = usable
+            .iter()
+            .map(|m| (m.arrival_time - reference_time) / m.error)
+            .collect();
+        standardized.sort_by(|a, b| a.total_cmp(b));
+        
+        let n = standardized.len() as f64;
+        let statistic = standardized
+            .iter()
+            .enumerate()
+            .map(|(i, &z)| {
+                let cdf = normal_cdf(z);
+                let above = (i + 1) as f64 / n - cdf;
+                let below = cdf - i as f64 / n;
+                above.max(below)
+            })
+            .fold(0.0, f64::max);
+        
+        let sqrt_n = n.sqrt();
+        let p_value = kolmogorov_survival((sqrt_n + 0.12 + 0.11 / sqrt_n) * statistic);
+        
+        KsResult {
+            statistic,
+            p_value,
+            is_normal: p_value >= 0.05,
+        }
+    }
+
+    /// Shapiro-Wilk test of whether `residuals` are normally distributed
+    ///
+    /// Preferred over `ks_normality_test` for small samples. The coefficients use
+    /// Royston's (1992) approximation to the expected normal order statistics, and the
+    /// p-value his normalizing transformation of `W` (exact for n = 3). Non-finite
+    /// values are ignored. The approximation holds for 3 ≤ n ≤ 5000 finite residuals;
+    /// outside that range, or when every residual is equal, nothing can be tested and
+    /// `W` and the p-value are both NaN.
+    pub fn shapiro_wilk(residuals: &[f64]) -> ShapiroResult {
+        let mut sorted: Vec<f64> = residuals.iter().copied().filter(|r| r.is_finite()).collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let sum_squares: f64 = sorted.iter().map(|r| (r - mean).powi(2)).sum();
+        if !(3..=5000).contains(&n) || sum_squares <= 0.0 {
+            return ShapiroResult { w: f64::NAN, p_value: f64::NAN };
+        }
+        
+        // Antisymmetric coefficients a_i; only the upper half is stored, a_{n+1-i} = -a_i
+        let coefficients = if n == 3 {
+            vec![std::f64::consts::FRAC_1_SQRT_2]
+        } else {
+            let nf = n as f64;
+            let m: Vec<f64> = (1..=n / 2)
+                .map(|i| normal_quantile((nf - i as f64 + 1.0 - 0.375) / (nf + 0.25)))
+                .collect();
+            let mm = 2.0 * m.iter().map(|v| v * v).sum::<f64>();
+            let u = 1.0 / nf.sqrt();
+            let mut a = vec![0.0; m.len()];
+            a[0] = m[0] / mm.sqrt()
+                + polynomial(&[0.0, 0.221157, -0.147981, -2.071190, 4.434685, -2.706056], u);
+            let (fixed, phi) = if n > 5 {
+                a[1] = m[1] / mm.sqrt()
+                    + polynomial(&[0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633], u);
+                let phi = (mm - 2.0 * m[0] * m[0] - 2.0 * m[1] * m[1])
+                    / (1.0 - 2.0 * a[0] * a[0] - 2.0 * a[1] * a[1]);
+                (2, phi)
+            } else {
+                (1, (mm - 2.0 * m[0] * m[0]) / (1.0 - 2.0 * a[0] * a[0]))
+            };
+            for (a, m) in a.iter_mut().zip(&m).skip(fixed) {
+                *a = m / phi.sqrt();
+            }
+            a
+        };
+        
+        let numerator: f64 = coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, a)| a * (sorted[n - 1 - i] - sorted[i]))
+            .sum();
+        let w = (numerator * numerator / sum_squares).min(1.0);
+        
+        let nf = n as f64;
+        let p_value = if n == 3 {
+            let p = 6.0 / std::f64::consts::PI * (w.sqrt().asin() - 0.75f64.sqrt().asin());
+            p.clamp(0.0, 1.0)
+        } else if n <= 11 {
+            let gamma = -2.273 + 0.459 * nf;
+            let mu = polynomial(&[0.5440, -0.39978, 0.025054, -0.0006714], nf);
+            let sigma = polynomial(&[1.3822, -0.77857, 0.062767, -0.0020322], nf).exp();
+            let z = (-(gamma - (1.0 - w).ln()).ln() - mu) / sigma;
+            1.0 - normal_cdf(z)
+        } else {
+            let ln_n = nf.ln();
+            let mu = polynomial(&[-1.5861, -0.31082, -0.083751, 0.0038915], ln_n);
+            let sigma = polynomial(&[-0.4803, -0.082676, 0.0030302], ln_n).exp();
+            let z = ((1.0 - w).ln() - mu) / sigma;
+            1.0 - normal_cdf(z)
+        };
+        
+        ShapiroResult { w, p_value }
+    }
+
+    /// Mann-Whitney U test comparing two arrival-time samples
+    ///
+    /// Ranks the pooled samples, giving tied values their average rank, and reports
+    /// `U` for the `low` sample: the number of (low, high) pairs in which the low-band
+    /// time is larger, counting ties as one half. The z-score uses the normal
+    /// approximation with the tie-corrected variance, and the p-value is two-sided.
+    /// Non-finite values are ignored. If either sample is empty, or every value is
+    /// tied, `z` is 0 and the p-value 1.
+    pub fn mann_whitney_u(low: &[f64], high: &[f64]) -> MwuResult {
+        let mut pooled: Vec<(f64, bool)> = low
+            .iter()
+            .map(|&t| (t, true))
+            .chain(high.iter().map(|&t| (t, false)))
+            .filter(|(t, _)| t.is_finite())
+            .collect();
+        pooled.sort_by(|a, b| a.0.total_cmp(&b.0));
+        
+        let n = pooled.len();
+        let n_low = pooled.iter().filter(|(_, is_low)| *is_low).count() as f64;
+        let n_high = n as f64 - n_low;
+        
+        let mut low_rank_sum = 0.0;
+        let mut tie_term = 0.0;
+        let mut start = 0;
+        while start < n {
+            let mut end = start;
+            while end + 1 < n && pooled[end + 1].0 == pooled[start].0 {
+                end += 1;
+            }
+            // Ranks are 1-based; tied values share the average of their ranks
+            let rank = (start + end) as f64 / 2.0 + 1.0;
+            let ties = (end - start + 1) as f64;
+            low_rank_sum += rank * pooled[start..=end].iter().filter(|(_, is_low)| *is_low).count() as f64;
+            tie_term += ties * ties * ties - ties;
+            start = end + 1;
+        }
+        
+        let u = low_rank_sum - n_low * (n_low + 1.0) / 2.0;
+        let total = n as f64;
+        let variance = if total > 1.0 {
+            n_low * n_high / 12.0 * ((total + 1.0) - tie_term / (total * (total - 1.0)))
+        } else {
+            0.0
+        };
+        if n_low == 0.0 || n_high == 0.0 || variance <= 0.0 {
+            return MwuResult { u, z: 0.0, p_value: 1.0 };
+        }
+        
+        let z = (u - n_low * n_high / 2.0) / variance.sqrt();
+        let p_value = (2.0 * (1.0 - normal_cdf(z.abs()))).min(1.0);
+        
+        MwuResult { u, z, p_value }
+    }
+
+    /// Likelihood-ratio test of constant c against a linear LIV delay
+    ///
+    /// The null model fits a common arrival time; the alternative adds a delay slope
+    /// proportional to energy. Both are fitted by maximum likelihood and `-2 ln(L0/L1)`
+    /// is referred to a chi-squared distribution with one degree of freedom (Wilks).
+    /// When the source distance is known (directly, or from a cosmology and the
+    /// measurements' redshift) the fitted slope is also expressed as an E_QG estimate.
+    pub fn likelihood_ratio_test(
+        measurements: &[TimingData],
+        cosmology: Option<&Cosmology>,
+        distance_m: Option<f64>,
+    ) -> LrtResult {
+        let usable: Vec<&TimingData> = measurements.iter().filter(|m| has_usable_error(m)).collect();
+        let null_chi_squared = Self::chi_squared_test(measurements).chi_squared;
+        let null_log_likelihood = gaussian_log_likelihood(&usable, null_chi_squared);
+        
+        let fit = weighted_linear_fit(usable.iter().copied());
+        let (alternative_log_likelihood, slope) = match fit {
+            Some(fit) => (gaussian_log_likelihood(&usable, fit.chi_squared), fit.slope),
+            None => (null_log_likelihood, 0.0),
+        };
+        
+        let test_statistic = (2.0 * (alternative_log_likelihood - null_log_likelihood)).max(0.0);
+        let p_value = chi_squared_pvalue(test_statistic, 1);
+        
+        let distance = distance_m.or_else(|| {
+            let z = usable.iter().find_map(|m| m.redshift)?;
+            Some(cosmology?.comoving_distance(z))
+        });
+        let energy_scale = distance
+            .filter(|_| slope != 0.0)
+            .map(|d| d / SPEED_OF_LIGHT / slope.abs());
+        
+        LrtResult {
+            null_log_likelihood,
+            alternative_log_likelihood,
+            test_statistic,
+            p_value,
+            slope,
+            energy_scale,
+        }
+    }
+
+    /// F-test of constant c against a linear LIV delay
+    ///
+    /// Each arrival time first has its light-travel time removed, taken from the
+    /// measurement's own distance, else from `distance_m`, else from its redshift and
+    /// `cosmology`. The weighted residual sum of squares of a common emission time
+    /// (one parameter) is then compared with that of a line in energy (two
+    /// parameters) through `F = (RSS0 - RSS1) / (RSS1 / (n - 2))`, referred to an F
+    /// distribution with (1, n - 2) degrees of freedom. With fewer than three usable
+    /// points or no energy spread the statistic is 0 and the p-value 1.
+    pub fn f_test_model_comparison(
+        measurements: &[TimingData],
+        cosmology: Option<&Cosmology>,
+        distance_m: Option<f64>,
+    ) -> FTestResult {
+        let emission_times: Vec<TimingData> = measurements
+            .iter()
+            .filter(|m| has_usable_error(m))
+            .map(|m| {
+                let travel_time = m
+                    .light_travel_time()
+                    .or_else(|| distance_m.map(|d| d / SPEED_OF_LIGHT))
+                    .or_else(|| Some(cosmology?.comoving_distance(m.redshift?) / SPEED_OF_LIGHT))
+                    .unwrap_or(0.0);
+                TimingData {
+                    arrival_time: m.arrival_time - travel_time,
+                    ..m.clone()
+                }
+            })
+            .collect();
+        
+        let dof_num = 1;
+        let dof_den = emission_times.len().saturating_sub(2);
+        let fit = match weighted_linear_fit(&emission_times).filter(|_| dof_den > 0) {
+            Some(fit) => fit,
+            None => {
+                return FTestResult {
+                    f_statistic: 0.0,
+                    dof_num,
+                    dof_den,
+                    p_value: 1.0,
+                }
+            }
+        };
+        
+        let null_rss = Self::chi_squared_test(&emission_times).chi_squared;
+        let reduction = (null_rss - fit.chi_squared).max(0.0);
+        let f_statistic = if fit.chi_squared > 0.0 {
+            reduction / dof_num as f64 / (fit.chi_squared / dof_den as f64)
+        } else if reduction > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        let p_value = if f_statistic.is_infinite() {
+            0.0
+        } else {
+            1.0 - f_distribution_cdf(f_statistic, dof_num as f64, dof_den as f64)
+        };
+        
+        FTestResult {
+            f_statistic,
+            dof_num,
+            dof_den,
+            p_value,
+        }
+    }
+
+    /// Akaike and Bayesian information criteria for competing fitted models
+    ///
+    /// Each model is given as `(name, log_likelihood, parameter_count)`, for example the
+    /// constant-c and LIV log-likelihoods from `likelihood_ratio_test` with one and two
+    /// parameters. AIC is `2k - 2 ln L` and BIC is `k ln(n) - 2 ln L`, where `n` counts
+    /// the measurements with a usable error. `delta_aic` is measured from the lowest
+    /// AIC, so the preferred model has 0. Results are returned in input order.
+    pub fn information_criteria(
+        measurements: &[TimingData],
+        models: &[(&str, f64, usize)],
+    ) -> Vec<IcResult> {
+        let n = measurements.iter().filter(|m| has_usable_error(m)).count() as f64;
+        let mut results: Vec<IcResult> = models
+            .iter()
+            .map(|&(name, log_likelihood, parameters)| {
+                let k = parameters as f64;
+                IcResult {
+                    model_name: name.to_string(),
+                    aic: 2.0 * k - 2.0 * log_likelihood,
+                    bic: k * n.ln() - 2.0 * log_likelihood,
+                    delta_aic: 0.0,
+                }
+            })
+            .collect();
+        
+        let best_aic = results.iter().map(|r| r.aic).fold(f64::INFINITY, f64::min);
+        for result in &mut results {
+            result.delta_aic = result.aic - best_aic;
         }
+        results
     }
 
     /// Performs Bayesian analysis to quantify speed of light deviation
+    ///
+    /// Uses a flat prior centred on zero that comfortably contains the least-squares
+    /// slope; see `bayesian_analysis_with_prior` for the model.
     pub fn bayesian_analysis(measurements: &[TimingData]) -> BayesianResult {
-        // For simplicity, we'll return a mock result
-        // In a real implementation, this would:
-        // 1. Define prior distributions for light speed deviation
-        // 2. Compute likelihood function based on measurements
-        // 3. Calculate posterior distribution
-        // 4. Estimate credible intervals
-        // 5. Compute Bayes factor for model comparison
+        let (slope, slope_error) = weighted_linear_fit(measurements)
+            .map_or((0.0, 1.0), |fit| (fit.slope, fit.slope_variance.sqrt()));
+        let half_width = slope.abs() + 10.0 * slope_error;
+        let prior_range = (-half_width, half_width);
+        Self::bayesian_analysis_with_prior(measurements, prior_range, DEFAULT_GRID_POINTS)
+    }
+
+    /// Grid posterior over the LIV time-delay slope η (seconds per GeV)
+    ///
+    /// Each arrival time is modelled as `t0 + η · E` with Gaussian errors, under a flat
+    /// prior on `prior_range`. The emission time `t0` is a nuisance parameter fixed at
+    /// its best fit for each η, which in the centred form used by `weighted_linear_fit`
+    /// measures times from the weighted mean arrival and energies from the weighted
+    /// mean energy. The evidence is the prior-weighted likelihood summed over the grid,
+    /// and the Bayes factor compares it against the constant-c model (η = 0), whose
+    /// best fit is the weighted mean arrival of `chi_squared_test`. Absolute arrival
+    /// times therefore do not affect the result. The grid is evaluated
+    /// deterministically, so no seed is needed and the same inputs always give
+    /// bit-identical results.
+    pub fn bayesian_analysis_with_prior(
+        measurements: &[TimingData],
+        prior_range: (f64, f64),
+        grid_points: usize,
+    ) -> BayesianResult {
+        let (low, high) = prior_range;
+        let grid_points = grid_points.max(2);
+        let spacing = (high - low) / (grid_points - 1) as f64;
+        let usable: Vec<&TimingData> = measurements.iter().filter(|m| has_usable_error(m)).collect();
+        let (mut total_weight, mut weighted_energy, mut weighted_time) = (0.0, 0.0, 0.0);
+        for m in &usable {
+            let weight = m.weighted_error().powi(-2);
+            total_weight += weight;
+            weighted_energy += weight * m.energy;
+            weighted_time += weight * m.arrival_time;
+        }
+        let (mean_energy, mean_time) = if total_weight > 0.0 {
+            (weighted_energy / total_weight, weighted_time / total_weight)
+        } else {
+            (0.0, 0.0)
+        };
+        let log_likelihood = |eta: f64| -> f64 {
+            usable
+                .iter()
+                .map(|m| {
+                    let error = m.weighted_error();
+                    let z = ((m.arrival_time - mean_time) - eta * (m.energy - mean_energy)) / error;
+                    -0.5 * z * z - error.ln() - 0.5 * (2.0 * std::f64::consts::PI).ln()
+                })
+                .sum()
+        };
+        
+        let grid: Vec<f64> = (0..grid_points).map(|i| low + i as f64 * spacing).collect();
+        let log_like: Vec<f64> = grid.iter().map(|&eta| log_likelihood(eta)).collect();
+        let max_log_like = log_like.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = log_like.iter().map(|l| (l - max_log_like).exp()).collect();
+        let weight_sum: f64 = weights.iter().sum();
+        
+        let posterior_mean =
+            grid.iter().zip(&weights).map(|(eta, w)| eta * w).sum::<f64>() / weight_sum;
+        
+        // Cumulative posterior for the equal-tailed 95% credible interval
+        let mut cumulative = Vec::with_capacity(grid_points);
+        let mut running = 0.0;
+        for w in &weights {
+            running += w / weight_sum;
+            cumulative.push(running);
+        }
+        let quantile = |q: f64| -> f64 {
+            let index = cumulative.iter().position(|&c| c >= q).unwrap_or(grid_points - 1);
+            if index == 0 {
+                return grid[0];
+            }
+            let (c0, c1) = (cumulative[index - 1], cumulative[index]);
+            let fraction = if c1 > c0 { (q - c0) / (c1 - c0) } else { 0.0 };
+            grid[index - 1] + fraction * spacing
+        };
+        
+        // Flat prior density 1 / (high - low), integrated with the grid spacing
+        let log_evidence = max_log_like + (weight_sum * spacing / (high - low)).ln();
+        let log_null_evidence = log_likelihood(0.0);
+        let log_bayes_factor = log_evidence - log_null_evidence;
         
         BayesianResult {
-            posterior_mean: 0.0,
-            credible_interval: (-1e-15, 1e-15),
-            evidence: 0.0,
+            posterior_mean,
+            credible_interval: (quantile(0.025), quantile(0.975)),
+            evidence: log_evidence.exp(),
             model_comparison: ModelComparison {
-                bayes_factor: 1.0,
-                model_evidence_ratio: 1.0,
+                bayes_factor: log_bayes_factor.exp(),
+                model_evidence_ratio: log_bayes_factor,
             },
         }
     }
 
     /// Calculates confidence intervals for speed of light measurements
+    ///
+    /// Uses a bootstrap with `DEFAULT_BOOTSTRAP_RESAMPLES` resamples and the default seed.
     pub fn estimate_confidence_intervals(measurements: &[TimingData]) -> ConfidenceInterval {
-        // For simplicity, we'll return a mock result
-        // In a real implementation, this would:
-        // 1. Calculate sample statistics from timing measurements
-        // 2. Apply appropriate statistical method (t-distribution, normal distribution)
-        // 3. Compute confidence bounds for light speed deviation
+        Self::bootstrap_confidence_interval(measurements, DEFAULT_BOOTSTRAP_RESAMPLES, None)
+    }
+
+    /// Nonparametric bootstrap 95% interval for the weighted deviation estimate
+    ///
+    /// The deviation estimate is the inverse-variance weighted mean arrival time, which
+    /// is the mean deviation when arrival times are residuals against the expected
+    /// arrivals. Measurements are resampled with replacement `resamples` times and the
+    /// empirical 2.5th and 97.5th percentiles of the estimates form the interval. The
+    /// same `seed` always yields the same interval; `None` uses `DEFAULT_BOOTSTRAP_SEED`.
+    pub fn bootstrap_confidence_interval(
+        measurements: &[TimingData],
+        resamples: usize,
+        seed: Option<u64>,
+    ) -> ConfidenceInterval {
+        let mut rng = StdRng::seed_from_u64(seed.unwrap_or(DEFAULT_BOOTSTRAP_SEED));
+        let n = measurements.len();
+        
+        let mut estimates: Vec<f64> = if n == 0 {
+            Vec::new()
+        } else {
+            (0..resamples)
+                .filter_map(|_| weighted_mean((0..n).map(|_| &measurements[rng.gen_range(0..n)])))
+                .collect()
+        };
+        estimates.sort_by(|a, b| a.total_cmp(b));
         
         ConfidenceInterval {
-            lower_bound: -1e-15,
-            upper_bound: 1e-15,
+            lower_bound: percentile_sorted(&estimates, 0.025),
+            upper_bound: percentile_sorted(&estimates, 0.975),
             confidence_level: 0.95,
-            method_used: "Normal approximation".to_string(),
+            method_used: "Bootstrap".to_string(),
+        }
+    }
+
+    /// Two-sided interval for the mean arrival-time deviation from the sample scatter
+    ///
+    /// The interval is `mean ± critical · s / √n`, where `s` is the sample standard
+    /// deviation. `CiMethod::Normal` takes the critical value from the standard normal
+    /// distribution; `CiMethod::StudentT` uses the t distribution with `n - 1` degrees
+    /// of freedom, which is wider and correct for small samples. Returns an error for
+    /// fewer than two measurements or a `confidence` outside (0, 1).
+    pub fn confidence_interval(
+        measurements: &[TimingData],
+        confidence: f64,
+        method: CiMethod,
+    ) -> Result<ConfidenceInterval, String> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(format!("confidence level must be in (0, 1), got {}", confidence));
+        }
+        let n = measurements.len();
+        if n < 2 {
+            return Err(format!("at least two measurements are required, got {}", n));
+        }
+        
+        let mean = stable_sum(measurements.iter().map(|m| m.arrival_time)) / n as f64;
+        let variance =
+            stable_sum(measurements.iter().map(|m| (m.arrival_time - mean).powi(2))) / (n - 1) as f64;
+        let standard_error = (variance / n as f64).sqrt();
+        
+        let p = 0.5 + confidence / 2.0;
+        let (critical, method_used) = match method {
+            CiMethod::Normal => (standard_normal_quantile(p), "Normal approximation"),
+            CiMethod::StudentT => (student_t_quantile(p, (n - 1) as f64), "Student-t"),
+        };
+        
+        Ok(ConfidenceInterval {
+            lower_bound: mean - critical * standard_error,
+            upper_bound: mean + critical * standard_error,
+            confidence_level: confidence,
+            method_used: method_used.to_string(),
+        })
+    }
+
+    /// Two-sample z-test of whether two deviation estimates are consistent
+    ///
+    /// Each estimate is a `(value, standard_error)` pair, such as the weighted-mean
+    /// deviation and its error from two datasets. The p-value is two-sided and the
+    /// estimates are consistent when it is at least `alpha`. Returns an error if `alpha`
+    /// is not in (0, 1) or either standard error is not positive and finite.
+    pub fn two_sample_z_test(
+        first: (f64, f64),
+        second: (f64, f64),
+        alpha: f64,
+    ) -> Result<ZTestResult, String> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(format!("significance level must be in (0, 1), got {}", alpha));
+        }
+        for (value, error) in [first, second] {
+            if !(value.is_finite() && error.is_finite() && error > 0.0) {
+                return Err(format!(
+                    "estimates need a finite value and positive standard error, got {} ± {}",
+                    value, error
+                ));
+            }
+        }
+        
+        let difference = first.0 - second.0;
+        let difference_error = first.1.hypot(second.1);
+        let z_score = difference / difference_error;
+        let p_value = 2.0 * (1.0 - normal_cdf(z_score.abs()));
+        
+        Ok(ZTestResult {
+            difference,
+            difference_error,
+            z_score,
+            p_value,
+            is_consistent: p_value >= alpha,
+        })
+    }
+
+    /// Combines per-source LIV delay slopes into one inverse-variance weighted limit
+    ///
+    /// Each entry is a `(slope, standard_error)` pair, such as `LivFitResult::slope` and
+    /// `slope_error` for one source. The consistency statistic is `Σ ((slope_i - slope)
+    /// / error_i)²` with one degree of freedom fewer than the number of sources; a small
+    /// p-value means the sources disagree beyond their errors. Entries without a finite
+    /// slope and positive, finite error are skipped. With no usable entries the slope
+    /// is NaN and its error infinite.
+    pub fn combine_limits(limits: &[(f64, f64)]) -> CombinedLimit {
+        let usable: Vec<(f64, f64)> = limits
+            .iter()
+            .copied()
+            .filter(|&(slope, error)| slope.is_finite() && error.is_finite() && error > 0.0)
+            .collect();
+        let mut weighted_slope = CompensatedSum::default();
+        let mut total_weight = CompensatedSum::default();
+        for &(slope, error) in &usable {
+            let weight = 1.0 / (error * error);
+            weighted_slope.add(weight * slope);
+            total_weight.add(weight);
+        }
+        if usable.is_empty() {
+            return CombinedLimit {
+                slope: f64::NAN,
+                slope_error: f64::INFINITY,
+                chi_squared: 0.0,
+                degrees_of_freedom: 0,
+                p_value: 1.0,
+                sources: 0,
+            };
+        }
+        
+        let slope = weighted_slope.total() / total_weight.total();
+        let chi_squared =
+            stable_sum(usable.iter().map(|&(value, error)| ((value - slope) / error).powi(2)));
+        let degrees_of_freedom = usable.len() - 1;
+        CombinedLimit {
+            slope,
+            slope_error: total_weight.total().sqrt().recip(),
+            chi_squared,
+            degrees_of_freedom,
+            p_value: chi_squared_pvalue(chi_squared, degrees_of_freedom),
+            sources: usable.len(),
         }
     }
 
     /// Tests null hypothesis that light speed is constant
-    pub fn hypothesis_test(measurements: &[TimingData], alpha: f64) -> HypothesisTestResult {
-        // For simplicity, we'll return a mock result
-        // In a real implementation, this would:
-        // 1. Set up null hypothesis (light speed is constant)
-        // 2. Set up alternative hypothesis (light speed varies)
-        // 3. Calculate test statistic from measurements
-        // 4. Compare against critical value or compute p-value
-        // 5. Make decision based on significance level
-        
-        HypothesisTestResult {
-            p_value: 0.5,
-            is_rejected: false,
-            significance_level: alpha,
-            test_statistic: 0.0,
+    ///
+    /// The test statistic is the weighted chi-squared of the arrival times against the
+    /// constant-c model; the null is rejected when its p-value falls below `alpha`.
+    /// Returns an error if `alpha` is not strictly between 0 and 1.
+    pub fn hypothesis_test(
+        measurements: &[TimingData],
+        alpha: f64,
+    ) -> Result<HypothesisTestResult, String> {
+        if !(alpha > 0.0 && alpha < 1.0) {
+            return Err(format!("significance level must be in (0, 1), got {}", alpha));
         }
+        
+        let chi = Self::chi_squared_test(measurements);
+        
+        Ok(HypothesisTestResult {
+            p_value: chi.p_value,
+            is_rejected: chi.p_value < alpha,
+            significance_level: alpha,
+            test_statistic: chi.chi_squared,
+        })
     }
 }
 
@@ -89,6 +881,69 @@ pub struct ChiSquaredResult {
     pub is_significant: bool,
 }
 
+/// Kolmogorov-Smirnov normality test result
+#[derive(Debug, Clone)]
+pub struct KsResult {
+    pub statistic: f64,
+    pub p_value: f64,
+    pub is_normal: bool,
+}
+
+/// Shapiro-Wilk normality test result
+#[derive(Debug, Clone)]
+pub struct ShapiroResult {
+    pub w: f64,                      // W statistic in (0, 1], near 1 if normal; NaN if untested
+    pub p_value: f64,                // Chance of a W this small under normality; NaN if untested
+}
+
+/// Inverse-variance weighted combination of per-source LIV slopes
+#[derive(Debug, Clone)]
+pub struct CombinedLimit {
+    pub slope: f64,                  // Combined delay slope in s/GeV
+    pub slope_error: f64,            // Standard error of the combined slope
+    pub chi_squared: f64,            // Scatter of the sources about the combined slope
+    pub degrees_of_freedom: usize,   // Sources used minus one
+    pub p_value: f64,                // Probability of this much scatter from consistent sources
+    pub sources: usize,              // Entries with a usable slope and error
+}
+
+/// Mann-Whitney U test result
+#[derive(Debug, Clone)]
+pub struct MwuResult {
+    pub u: f64,                      // U statistic of the first sample
+    pub z: f64,                      // Normal-approximation z-score
+    pub p_value: f64,                // Two-sided
+}
+
+/// Likelihood-ratio test result for constant c versus linear LIV
+#[derive(Debug, Clone)]
+pub struct LrtResult {
+    pub null_log_likelihood: f64,
+    pub alternative_log_likelihood: f64,
+    pub test_statistic: f64,         // -2 ln(L0 / L1)
+    pub p_value: f64,
+    pub slope: f64,                  // Fitted delay slope in s/GeV
+    pub energy_scale: Option<f64>,   // Implied E_QG in GeV, if the distance is known
+}
+
+/// F-test result for constant c versus linear LIV
+#[derive(Debug, Clone)]
+pub struct FTestResult {
+    pub f_statistic: f64,
+    pub dof_num: usize,              // Extra parameters in the LIV model
+    pub dof_den: usize,              // Residual degrees of freedom of the LIV model
+    pub p_value: f64,
+}
+
+/// Information criteria for one fitted model
+#[derive(Debug, Clone)]
+pub struct IcResult {
+    pub model_name: String,
+    pub aic: f64,
+    pub bic: f64,
+    pub delta_aic: f64,              // AIC minus the lowest AIC among the compared models
+}
+
 /// Bayesian analysis result
 #[derive(Debug, Clone)]
 pub struct BayesianResult {
@@ -101,8 +956,15 @@ pub struct BayesianResult {
 /// Model comparison between light speed constancy models
 #[derive(Debug, Clone)]
 pub struct ModelComparison {
-    pub bayes_factor: f64,
-    pub model_evidence_ratio: f64,
+    pub bayes_factor: f64,          // evidence(variable c) / evidence(constant c)
+    pub model_evidence_ratio: f64,  // natural log of the Bayes factor
+}
+
+/// Critical-value distribution for `SpeedOfLightHypothesisTest::confidence_interval`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiMethod {
+    Normal,
+    StudentT,
 }
 
 /// Confidence interval estimate
@@ -114,6 +976,16 @@ pub struct ConfidenceInterval {
     pub method_used: String,
 }
 
+/// Two-sample z-test result
+#[derive(Debug, Clone, Serialize)]
+pub struct ZTestResult {
+    pub difference: f64,        // first minus second estimate
+    pub difference_error: f64,  // Standard error of the difference
+    pub z_score: f64,
+    pub p_value: f64,           // Two-sided
+    pub is_consistent: bool,
+}
+
 /// Hypothesis test result
 #[derive(Debug, Clone)]
 pub struct HypothesisTestResult {
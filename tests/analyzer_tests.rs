@@ -0,0 +1,2052 @@
+use lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+use lightspeedvalidator::stats_math::stable_sum;
+use lightspeedvalidator::{
+    log_bin_edges, AnalysisError, AnomalyKind, Cosmology, DispersionOrder, GammaRayAnalyzer,
+    LossKind, MergeError, QuantumGravityModel, SourceGeometry, TimeReference, TimingData,
+    TimingDataError,
+};
+
+/// Analyzer whose quantum gravity delays are E² seconds against unit errors,
+/// giving significances of 0.25, 2.25, 4 and 6.25 sigma.
+fn graded_anomaly_analyzer() -> GammaRayAnalyzer {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.enable_quantum_gravity_simulation(QuantumGravityModel {
+        planck_length: 1.616e-35,
+        quantum_gravity_effect: SPEED_OF_LIGHT * SPEED_OF_LIGHT,
+        energy_scale: 1e19,
+        dispersion_order: DispersionOrder::Quadratic,
+    });
+    for &energy in &[0.5, 1.5, 2.0, 2.5] {
+        analyzer.add_measurement(energy, 1000.0, 1.0);
+    }
+    analyzer
+}
+
+#[test]
+fn test_detect_anomalies_default_three_sigma() {
+    let analyzer = graded_anomaly_analyzer();
+    assert_eq!(analyzer.detect_anomalies().len(), 2);
+}
+
+#[test]
+fn test_lower_anomaly_sigma_surfaces_more() {
+    let mut analyzer = graded_anomaly_analyzer();
+    let default_count = analyzer.detect_anomalies().len();
+    analyzer.set_anomaly_sigma(1.0);
+    let count = analyzer.detect_anomalies().len();
+    assert!(count > default_count);
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn test_higher_anomaly_sigma_surfaces_fewer() {
+    let mut analyzer = graded_anomaly_analyzer();
+    let default_count = analyzer.detect_anomalies().len();
+    analyzer.set_anomaly_sigma(5.0);
+    let count = analyzer.detect_anomalies().len();
+    assert!(count < default_count);
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_top_anomalies_sorted_by_significance_with_tie_breaks() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.set_reference_time(1000.0);
+    let photons = [(5.0, 10.0), (1.0, -4.0), (3.0, 10.0), (2.0, 0.0), (4.0, -10.0), (6.0, 7.0)];
+    for &(energy, offset) in photons.iter().chain(&[(3.0, -10.0)]) {
+        analyzer.add_measurement(energy, 1000.0 + offset, 1.0);
+    }
+
+    let top = analyzer.top_anomalies(usize::MAX);
+    assert_eq!(top.len(), analyzer.detect_anomalies().len());
+    let order: Vec<(f64, f64)> = top.iter().map(|a| (a.energy, a.deviation)).collect();
+    assert_eq!(
+        order,
+        vec![(3.0, 10.0), (3.0, -10.0), (4.0, -10.0), (5.0, 10.0), (6.0, 7.0), (1.0, -4.0)]
+    );
+
+    let top_two = analyzer.top_anomalies(2);
+    assert_eq!(top_two.len(), 2);
+    assert_eq!((top_two[1].energy, top_two[1].deviation), (3.0, -10.0));
+    assert!(analyzer.top_anomalies(0).is_empty());
+}
+
+#[test]
+fn test_anomalies_split_by_arrival_direction() {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for &(energy, offset) in &[(1.0, 0.0), (2.0, -50.0), (3.0, 0.0), (4.0, 50.0)] {
+        analyzer.add_measurement_with_distance(energy, travel_time + offset, 1.0, distance);
+    }
+
+    let (early, late) = analyzer.detect_anomalies_by_kind();
+    assert_eq!(early.len(), 1);
+    assert_eq!(late.len(), 1);
+    assert_eq!(early[0].energy, 2.0);
+    assert_eq!(early[0].kind, AnomalyKind::EarlyArrival);
+    assert!(early[0].deviation < 0.0);
+    assert_eq!(late[0].energy, 4.0);
+    assert_eq!(late[0].kind, AnomalyKind::LateArrival);
+    assert!(late[0].deviation > 0.0);
+}
+
+#[test]
+#[should_panic(expected = "anomaly sigma must be positive and finite")]
+fn test_anomaly_sigma_rejects_non_positive() {
+    GammaRayAnalyzer::new().set_anomaly_sigma(0.0);
+}
+
+#[test]
+#[should_panic(expected = "anomaly sigma must be positive and finite")]
+fn test_anomaly_sigma_rejects_nan() {
+    GammaRayAnalyzer::new().set_anomaly_sigma(f64::NAN);
+}
+
+#[test]
+fn test_expected_arrival_is_light_travel_time() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement_with_distance(100.0, 1.0, 0.001, SPEED_OF_LIGHT);
+    let expected = analyzer.calculate_expected_arrivals();
+    assert!((expected[0].1 - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_expected_arrival_scales_linearly_with_distance() {
+    let base_distance = 1.0e20;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for factor in 1..=4 {
+        analyzer.add_measurement_with_distance(100.0, 0.0, 0.001, base_distance * factor as f64);
+    }
+    let expected = analyzer.calculate_expected_arrivals();
+    let unit = expected[0].1;
+    assert!((unit - base_distance / SPEED_OF_LIGHT).abs() / unit < 1e-12);
+    for (i, &(_, time)) in expected.iter().enumerate() {
+        let ratio = time / unit;
+        assert!((ratio - (i + 1) as f64).abs() < 1e-12, "ratio {} at index {}", ratio, i);
+    }
+}
+
+#[test]
+fn test_unknown_distance_defaults_to_earliest_arrival() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(100.0, 1234567890.123, 0.001);
+    analyzer.add_measurement(200.0, 1234567890.5, 0.001);
+    assert_eq!(analyzer.reference_time(), 1234567890.123);
+    let expected = analyzer.calculate_expected_arrivals();
+    assert_eq!(expected[0].1, 1234567890.123);
+    assert_eq!(expected[1].1, 1234567890.123);
+}
+
+fn self_referenced_dispersed_analyzer(t0: f64) -> GammaRayAnalyzer {
+    // Arrivals drift 0.01 s/GeV after t0, with no source geometry
+    let mut analyzer = GammaRayAnalyzer::new();
+    for energy in 1..=10 {
+        let energy = energy as f64;
+        analyzer.add_measurement(energy, t0 + 0.01 * energy, 0.001);
+    }
+    analyzer
+}
+
+#[test]
+fn test_reference_time_makes_constant_c_test_meaningful() {
+    let t0 = 1000.0;
+    let mut analyzer = self_referenced_dispersed_analyzer(t0);
+    analyzer.set_reference_time(t0);
+    assert_eq!(analyzer.reference_time(), t0);
+    let result = analyzer.test_light_speed_constancy();
+    // Residuals of 10·E σ give χ² = 100 · Σ E² = 38500
+    assert!((result.chi_squared - 38500.0).abs() < 1e-3, "{}", result.chi_squared);
+    assert!(!result.is_valid);
+
+    // A model reproducing the drift explains the residuals away
+    let mut model = liv_model(DispersionOrder::Linear);
+    model.quantum_gravity_effect = 0.01 * SPEED_OF_LIGHT;
+    analyzer.enable_quantum_gravity_simulation(model);
+    assert!(analyzer.test_light_speed_constancy().chi_squared < 1e-6);
+}
+
+#[test]
+fn test_reference_time_change_invalidates_cache() {
+    let mut analyzer = self_referenced_dispersed_analyzer(1000.0);
+    assert_eq!(analyzer.calculate_expected_arrivals()[0].1, 1000.01);
+    analyzer.set_reference_time(999.0);
+    assert_eq!(analyzer.calculate_expected_arrivals()[0].1, 999.0);
+    assert_eq!(analyzer.config().reference_time, Some(999.0));
+}
+
+#[test]
+#[should_panic(expected = "reference time must be finite")]
+fn test_reference_time_rejects_nan() {
+    GammaRayAnalyzer::new().set_reference_time(f64::NAN);
+}
+
+#[test]
+fn test_time_reference_conversions() {
+    let trigger = TimeReference::SecondsSinceTrigger { t0: 1_700_000_000.0 };
+    let met = TimeReference::MissionElapsedTime { epoch: 978_307_200.0 };
+    assert_eq!(TimeReference::default(), TimeReference::UnixEpoch);
+    assert_eq!(TimeReference::UnixEpoch.to_unix(12.5), 12.5);
+    assert_eq!(trigger.to_unix(2.25), 1_700_000_002.25);
+    assert_eq!(trigger.from_unix(1_700_000_002.25), 2.25);
+    assert_eq!(trigger.convert(2.25, met), 721_692_802.25);
+    assert_eq!(met.convert(721_692_802.25, trigger), 2.25);
+    assert_eq!(trigger.convert(-0.5, TimeReference::UnixEpoch), 1_699_999_999.5);
+}
+
+#[test]
+fn test_reference_time_is_in_the_current_time_reference() {
+    let t0 = 1_700_000_000.0;
+    let mut relative = self_referenced_dispersed_analyzer(0.0);
+    let mut trigger = GammaRayAnalyzer::new();
+    trigger.set_time_reference(TimeReference::SecondsSinceTrigger { t0 });
+    for energy in 1..=10 {
+        let energy = energy as f64;
+        trigger.add_measurement(energy, 0.01 * energy, 0.001);
+    }
+    relative.set_reference_time(0.0);
+    trigger.set_reference_time(0.0);
+    assert_eq!(trigger.reference_time(), 0.0);
+    assert_eq!(trigger.config().reference_time, Some(t0));
+    assert_eq!(trigger.calculate_expected_arrivals()[0].1, t0);
+
+    // Unix times near t0 are spaced 2.4e-7 s apart, so the arrivals are rounded
+    let expected = relative.test_light_speed_constancy().chi_squared;
+    let actual = trigger.test_light_speed_constancy().chi_squared;
+    assert!((actual / expected - 1.0).abs() < 1e-3, "{} vs {}", actual, expected);
+}
+
+#[test]
+fn test_time_window_is_in_the_current_time_reference() {
+    let t0 = 1_700_000_000.0;
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.set_time_reference(TimeReference::SecondsSinceTrigger { t0 });
+    for offset in [-2.0, -0.5, 0.0, 0.5, 1.0, 3.0] {
+        analyzer.add_measurement(1.0, offset, 0.01);
+    }
+    assert_eq!(analyzer.filter_time_window(-1.0, 1.0), 4);
+    let kept: Vec<f64> = analyzer.measurements().iter().map(|m| m.arrival_time - t0).collect();
+    assert_eq!(kept, vec![-0.5, 0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn test_residuals_are_time_reference_invariant() {
+    let t0 = 1_700_000_000.0;
+    let epoch = 978_307_200.0;
+    let offsets = [0.0, 0.25, 1.5, 0.75, 3.0, 2.5];
+    let quantiles = [0.0, 0.5, 1.0];
+
+    let mut unix = GammaRayAnalyzer::new();
+    let mut mixed = GammaRayAnalyzer::new();
+    mixed.set_time_reference(TimeReference::SecondsSinceTrigger { t0 });
+    for (i, offset) in offsets.iter().enumerate() {
+        let energy = (i + 1) as f64;
+        unix.add_measurement(energy, t0 + offset, 0.5);
+        if i == 3 {
+            // The second half comes from an instrument counting mission elapsed time
+            mixed.set_time_reference(TimeReference::MissionElapsedTime { epoch });
+        }
+        let local = TimeReference::UnixEpoch.convert(t0 + offset, mixed.time_reference());
+        mixed.add_measurement(energy, local, 0.5);
+    }
+
+    assert_eq!(mixed.reference_time(), unix.reference_time() - epoch);
+    assert_eq!(mixed.residual_quantiles(&quantiles), unix.residual_quantiles(&quantiles));
+    let expected = unix.test_light_speed_constancy();
+    let actual = mixed.test_light_speed_constancy();
+    assert_eq!(actual.chi_squared, expected.chi_squared);
+    assert_eq!(actual.deviation_estimate, expected.deviation_estimate);
+    assert_eq!(mixed.config().time_reference, TimeReference::MissionElapsedTime { epoch });
+}
+
+fn liv_model(order: DispersionOrder) -> QuantumGravityModel {
+    QuantumGravityModel {
+        planck_length: 1.616e-35,
+        quantum_gravity_effect: 0.0,
+        energy_scale: 1e19,
+        dispersion_order: order,
+    }
+}
+
+#[test]
+fn test_linear_dispersion_delay_formula() {
+    let model = liv_model(DispersionOrder::Linear);
+    let travel_time = 1.0e17;
+    let delay = model.time_delay(100.0, Some(travel_time));
+    assert!((delay - 100.0 / 1e19 * travel_time).abs() < 1e-15);
+}
+
+#[test]
+fn test_linear_and_quadratic_delays_differ_for_same_energy() {
+    let travel_time = 1.0e17;
+    let linear = liv_model(DispersionOrder::Linear).time_delay(100.0, Some(travel_time));
+    let quadratic = liv_model(DispersionOrder::Quadratic).time_delay(100.0, Some(travel_time));
+    // Quadratic carries an extra E/E_QG suppression and the (1+n)/2 = 3/2 prefactor
+    assert!((quadratic / linear - 1.5 * 100.0 / 1e19).abs() < 1e-25);
+}
+
+#[test]
+fn test_dispersion_delay_energy_scaling() {
+    let travel_time = 1.0e17;
+    let cases = [(DispersionOrder::Linear, 2.0), (DispersionOrder::Quadratic, 4.0)];
+    for (order, expected_ratio) in cases {
+        let model = liv_model(order);
+        let ratio =
+            model.time_delay(200.0, Some(travel_time)) / model.time_delay(100.0, Some(travel_time));
+        assert!((ratio - expected_ratio).abs() < 1e-12, "{:?} ratio {}", order, ratio);
+    }
+}
+
+#[test]
+fn test_expected_arrivals_include_linear_delay() {
+    let mut model = liv_model(DispersionOrder::Linear);
+    model.energy_scale = 1.0e4;
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.enable_quantum_gravity_simulation(model);
+    analyzer.add_measurement_with_distance(1000.0, 0.0, 0.001, SPEED_OF_LIGHT * 10.0);
+    let expected = analyzer.calculate_expected_arrivals();
+    // 10 s time of flight plus (1000 / 1e4) * 10 s of dispersion delay
+    assert!((expected[0].1 - 11.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_add_measurements_batch() {
+    let data: Vec<TimingData> = (0..1000)
+        .map(|i| TimingData::new(100.0 + i as f64, 1000.0 + i as f64 * 0.01, 0.001))
+        .collect();
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(50.0, 999.0, 0.001);
+    analyzer.add_measurements(&data);
+
+    assert_eq!(analyzer.len(), data.len() + 1);
+    assert_eq!(analyzer.measurements().len(), analyzer.len());
+    assert_eq!(analyzer.measurements()[0].energy, 50.0);
+    for (stored, original) in analyzer.measurements()[1..].iter().zip(&data) {
+        assert_eq!(stored.energy, original.energy);
+        assert_eq!(stored.arrival_time, original.arrival_time);
+    }
+}
+
+#[test]
+fn test_add_measurements_empty_slice() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurements(&[]);
+    assert!(analyzer.is_empty());
+}
+
+#[test]
+fn test_fit_liv_slope_recovers_injected_slope() {
+    let injected = 0.02;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=50 {
+        let energy = i as f64 * 2.0;
+        let scatter = if i % 3 == 0 { 0.01 } else { -0.005 };
+        analyzer.add_measurement(energy, 5.0 + injected * energy + scatter, 0.01);
+    }
+    let fit = analyzer.fit_liv_slope();
+    assert!(fit.slope_error > 0.0);
+    assert!(
+        (fit.slope - injected).abs() < 3.0 * fit.slope_error,
+        "slope {} ± {}",
+        fit.slope,
+        fit.slope_error
+    );
+    assert!(fit.reduced_chi_squared < 2.0);
+    assert!(fit.energy_scale.is_none());
+}
+
+#[test]
+fn test_fit_liv_slope_energy_scale_from_distance() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    // d/c = 100 s and slope 0.01 s/GeV give E_QG = 1e4 GeV
+    for i in 1..=10 {
+        let energy = i as f64 * 10.0;
+        analyzer.add_measurement_with_distance(energy, 0.01 * energy, 0.001, SPEED_OF_LIGHT * 100.0);
+    }
+    let fit = analyzer.fit_liv_slope();
+    let energy_scale = fit.energy_scale.unwrap();
+    assert!((energy_scale - 1.0e4).abs() / 1.0e4 < 1e-6, "{}", energy_scale);
+}
+
+#[test]
+fn test_fit_liv_slope_degenerate_input() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    assert!(analyzer.fit_liv_slope().slope.is_nan());
+    analyzer.add_measurement(100.0, 1.0, 0.01);
+    analyzer.add_measurement(100.0, 2.0, 0.01);
+    assert!(analyzer.fit_liv_slope().slope.is_nan());
+}
+
+#[test]
+fn test_regression_recovers_slope_and_intercept() {
+    let (slope, intercept) = (0.02, 5.0);
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=50 {
+        let energy = i as f64 * 2.0;
+        let scatter = if i % 3 == 0 { 0.01 } else { -0.005 };
+        analyzer.add_measurement(energy, intercept + slope * energy + scatter, 0.01);
+    }
+    let fit = analyzer.regress_time_on_energy().unwrap();
+    assert!((fit.slope - slope).abs() < 3.0 * fit.slope_error, "slope {} ± {}", fit.slope, fit.slope_error);
+    assert!(
+        (fit.intercept - intercept).abs() < 3.0 * fit.intercept_error,
+        "intercept {} ± {}",
+        fit.intercept,
+        fit.intercept_error
+    );
+    // Positive energies anti-correlate the slope and intercept estimates
+    assert!(fit.slope_intercept_covariance < 0.0);
+    assert!(fit.r_squared > 0.99 && fit.r_squared <= 1.0, "{}", fit.r_squared);
+}
+
+#[test]
+fn test_confidence_band_is_narrowest_at_mean_energy() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=50 {
+        let energy = i as f64 * 2.0;
+        let scatter = if i % 3 == 0 { 0.01 } else { -0.005 };
+        analyzer.add_measurement(energy, 5.0 + 0.02 * energy + scatter, 0.01);
+    }
+    let fit = analyzer.regress_time_on_energy().unwrap();
+    assert_eq!(fit.degrees_of_freedom, 48);
+
+    // Equal weights put the narrowest point at the mean energy of 51 GeV
+    let energies = [-50.0, 0.0, 25.0, 51.0, 77.0, 102.0, 150.0];
+    let band = fit.confidence_band(&energies, 0.6827);
+    let widths: Vec<f64> = band.iter().map(|(lower, upper)| upper - lower).collect();
+    assert!(widths[..3].windows(2).all(|w| w[0] > w[1]), "{:?}", widths);
+    assert!(widths[3..].windows(2).all(|w| w[0] < w[1]), "{:?}", widths);
+    assert!((widths[2] - widths[4]).abs() < 1e-12, "{:?}", widths);
+    for (&energy, (lower, upper)) in energies.iter().zip(&band) {
+        let fitted = fit.intercept + fit.slope * energy;
+        assert!((0.5 * (lower + upper) - fitted).abs() < 1e-12);
+    }
+
+    // A wider confidence level scales every half-width by the same factor
+    let wide = fit.confidence_band(&energies, 0.95);
+    let ratio = (wide[0].1 - wide[0].0) / widths[0];
+    assert!(ratio > 1.9 && ratio < 2.1, "{}", ratio);
+}
+
+#[test]
+fn test_confidence_band_without_degrees_of_freedom_is_nan() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 1.0, 0.1);
+    analyzer.add_measurement(2.0, 2.0, 0.1);
+    let fit = analyzer.regress_time_on_energy().unwrap();
+    assert_eq!(fit.degrees_of_freedom, 0);
+    let band = fit.confidence_band(&[1.5], 0.9);
+    assert!(band[0].0.is_nan() && band[0].1.is_nan());
+}
+
+#[test]
+#[should_panic(expected = "confidence must be in (0, 1)")]
+fn test_confidence_band_rejects_invalid_confidence() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 1.0, 0.1);
+    analyzer.add_measurement(2.0, 2.0, 0.1);
+    analyzer.regress_time_on_energy().unwrap().confidence_band(&[1.0], 1.0);
+}
+
+#[test]
+fn test_monte_carlo_pvalue_detects_dispersion() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=30 {
+        let energy = i as f64;
+        let scatter = if i % 2 == 0 { 0.05 } else { -0.05 };
+        analyzer.add_measurement(energy, 1000.0 + 0.1 * energy + scatter, 0.05);
+    }
+    let p_value = analyzer.monte_carlo_pvalue(500, 7);
+    // No shuffle reaches the observed slope, leaving only the observed pairing
+    assert_eq!(p_value, 1.0 / 501.0);
+    assert_eq!(analyzer.monte_carlo_pvalue(500, 7), p_value);
+}
+
+#[test]
+fn test_monte_carlo_pvalue_of_uncorrelated_data_is_typical() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=30 {
+        analyzer.add_measurement(i as f64, 1000.0 + rng.gen_range(-0.1..0.1), 0.05);
+    }
+    let p_value = analyzer.monte_carlo_pvalue(1000, 11);
+    assert!(p_value > 0.2 && p_value < 0.8, "{}", p_value);
+}
+
+#[test]
+fn test_monte_carlo_pvalue_degenerate_input() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 1.0, 0.1);
+    assert!(analyzer.monte_carlo_pvalue(10, 0).is_nan());
+}
+
+#[test]
+#[should_panic(expected = "at least one Monte Carlo iteration is required")]
+fn test_monte_carlo_pvalue_rejects_zero_iterations() {
+    graded_anomaly_analyzer().monte_carlo_pvalue(0, 0);
+}
+
+/// Analyzer of undispersed photons at `count` energies spread evenly up to `e_max`
+fn forecast_analyzer(count: usize, e_max: f64, error: f64) -> GammaRayAnalyzer {
+    let distance = SPEED_OF_LIGHT * 1.0e17;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=count {
+        let energy = e_max * i as f64 / count as f64;
+        analyzer.add_measurement_with_distance(energy, 1.0e17, error, distance);
+    }
+    analyzer
+}
+
+#[test]
+fn test_sensitivity_forecast_grows_with_lever_arm_and_precision() {
+    let travel_time = 1.0e17;
+    let reach = |analyzer: &GammaRayAnalyzer| {
+        travel_time / analyzer.sensitivity_forecast(0.95).unwrap()
+    };
+
+    let baseline = forecast_analyzer(20, 10.0, 0.1);
+    let wider = forecast_analyzer(20, 100.0, 0.1);
+    let sharper = forecast_analyzer(20, 10.0, 0.01);
+    assert!(reach(&wider) > reach(&baseline));
+    assert!(reach(&sharper) > reach(&baseline));
+    // Errors ten times smaller shrink the detectable slope tenfold
+    let ratio = reach(&sharper) / reach(&baseline);
+    assert!((ratio - 10.0).abs() < 1e-9, "{}", ratio);
+
+    // The forecast is z · σ_slope of the least-squares fit
+    let slope_error = baseline.fit_liv_slope().slope_error;
+    let forecast = baseline.sensitivity_forecast(0.95).unwrap();
+    assert!((forecast / slope_error - 1.6449).abs() < 1e-3, "{}", forecast / slope_error);
+}
+
+#[test]
+fn test_sensitivity_forecast_rejects_degenerate_input() {
+    let analyzer = forecast_analyzer(20, 10.0, 0.1);
+    assert!(analyzer.sensitivity_forecast(0.5).is_none());
+    assert!(analyzer.sensitivity_forecast(1.0).is_none());
+    assert!(forecast_analyzer(1, 10.0, 0.1).sensitivity_forecast(0.95).is_none());
+}
+
+#[test]
+fn test_jackknife_flags_injected_outlier() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=20 {
+        let energy = i as f64 * 5.0;
+        let scatter = if i % 2 == 0 { 0.005 } else { -0.005 };
+        let outlier = if i == 17 { 2.0 } else { 0.0 };
+        analyzer.add_measurement(energy, 1.0 + 0.01 * energy + scatter + outlier, 0.01);
+    }
+    let jackknife = analyzer.jackknife_slope().unwrap();
+    assert_eq!(jackknife.most_influential, 16);
+    assert!(jackknife.standard_error > analyzer.fit_liv_slope().slope_error);
+
+    analyzer.remove_measurement(16);
+    let clean = analyzer.jackknife_slope().unwrap();
+    assert!((clean.mean_slope - 0.01).abs() < 1e-4, "{:?}", clean);
+    assert!(clean.standard_error < jackknife.standard_error);
+}
+
+#[test]
+fn test_jackknife_needs_three_measurements() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 1.0, 0.1);
+    analyzer.add_measurement(2.0, 2.0, 0.1);
+    assert!(analyzer.jackknife_slope().is_none());
+    analyzer.add_measurement(3.0, 3.0, 0.1);
+    let jackknife = analyzer.jackknife_slope().unwrap();
+    assert!((jackknife.mean_slope - 1.0).abs() < 1e-12);
+    assert!(jackknife.standard_error < 1e-9);
+}
+
+/// 20 photons on `t = 1 + 0.01·E` with ±0.5σ scatter and one photon 200σ late
+fn single_outlier_analyzer() -> GammaRayAnalyzer {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=20 {
+        let energy = i as f64 * 5.0;
+        let scatter = if i % 2 == 0 { 0.005 } else { -0.005 };
+        let outlier = if i == 17 { 2.0 } else { 0.0 };
+        analyzer.add_measurement(energy, 1.0 + 0.01 * energy + scatter + outlier, 0.01);
+    }
+    analyzer
+}
+
+/// Area 10 below 10 GeV rising to 1000 above 50 GeV
+fn rising_effective_area() -> Vec<(f64, f64)> {
+    vec![(1.0, 10.0), (10.0, 10.0), (50.0, 1000.0), (1000.0, 1000.0)]
+}
+
+#[test]
+fn test_effective_area_interpolates_log_log() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    assert_eq!(analyzer.effective_area_at(20.0), None);
+    analyzer.load_effective_area(rising_effective_area());
+    assert_eq!(analyzer.effective_area_at(0.1), Some(10.0));
+    assert_eq!(analyzer.effective_area_at(5.0), Some(10.0));
+    // Halfway between 10 and 50 GeV in log-energy is halfway in log-area
+    let area = analyzer.effective_area_at(10.0 * 5.0_f64.sqrt()).unwrap();
+    assert!((area - 100.0).abs() < 1e-9, "{}", area);
+    assert_eq!(analyzer.effective_area_at(5000.0), Some(1000.0));
+    assert_eq!(analyzer.config().effective_area, Some(rising_effective_area()));
+}
+
+#[test]
+fn test_effective_area_down_weights_low_area_photons() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.set_reference_time(1000.0);
+    // Low-area photons arrive 1 s late, high-area photons 1 s early
+    for &(energy, offset) in &[(2.0, 1.0), (5.0, 1.0), (100.0, -1.0), (200.0, -1.0)] {
+        analyzer.add_measurement(energy, 1000.0 + offset, 1.0);
+    }
+    let unweighted = analyzer.test_light_speed_constancy();
+    assert!(unweighted.deviation_estimate.unwrap().abs() < 1e-12);
+
+    analyzer.load_effective_area(rising_effective_area());
+    let weighted = analyzer.test_light_speed_constancy();
+    // Relative areas of 0.01 and 1 give a mean of (0.02 - 2) / 2.02
+    let expected = (0.02 - 2.0) / 2.02;
+    let deviation = weighted.deviation_estimate.unwrap();
+    assert!((deviation - expected).abs() < 1e-12, "{}", deviation);
+    assert!(weighted.chi_squared < unweighted.chi_squared);
+    assert!((analyzer.weighted_mean_arrival().unwrap() - (1000.0 + expected)).abs() < 1e-9);
+
+    analyzer.clear_effective_area();
+    assert!(analyzer.test_light_speed_constancy().deviation_estimate.unwrap().abs() < 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "effective-area energies must be strictly increasing")]
+fn test_effective_area_rejects_unsorted_curve() {
+    GammaRayAnalyzer::new().load_effective_area(vec![(10.0, 1.0), (1.0, 2.0)]);
+}
+
+#[test]
+#[should_panic(expected = "effective-area energies and areas must be positive and finite")]
+fn test_effective_area_rejects_non_positive_area() {
+    GammaRayAnalyzer::new().load_effective_area(vec![(1.0, 1.0), (10.0, 0.0)]);
+}
+
+#[test]
+fn test_huber_loss_resists_single_outlier() {
+    let mut analyzer = single_outlier_analyzer();
+    let least_squares = analyzer.fit_liv_slope();
+    let least_squares_chi_squared = analyzer.test_light_speed_constancy().chi_squared;
+
+    analyzer.set_loss(LossKind::Huber { delta: 1.345 });
+    let huber = analyzer.fit_liv_slope();
+    let least_squares_bias = (least_squares.slope - 0.01).abs();
+    let huber_bias = (huber.slope - 0.01).abs();
+    assert!(least_squares_bias > 10.0 * least_squares.slope_error, "{:?}", least_squares);
+    assert!(huber_bias < 0.05 * least_squares_bias, "{} vs {}", huber.slope, least_squares.slope);
+    assert!(huber.reduced_chi_squared < least_squares.reduced_chi_squared);
+    assert_eq!(analyzer.regress_time_on_energy().unwrap().slope, huber.slope);
+    assert!(analyzer.test_light_speed_constancy().chi_squared < least_squares_chi_squared);
+    assert_eq!(analyzer.config().loss, LossKind::Huber { delta: 1.345 });
+}
+
+#[test]
+fn test_huber_loss_matches_least_squares_without_outliers() {
+    let mut analyzer = single_outlier_analyzer();
+    analyzer.remove_measurement(16);
+    let least_squares = analyzer.fit_liv_slope();
+
+    // Every residual is within delta, so no weight is changed
+    analyzer.set_loss(LossKind::Huber { delta: 5.0 });
+    let huber = analyzer.fit_liv_slope();
+    assert!((huber.slope - least_squares.slope).abs() < 1e-12, "{:?}", huber);
+    assert_eq!(huber.slope_error, least_squares.slope_error);
+}
+
+#[test]
+#[should_panic(expected = "Huber delta must be positive and finite")]
+fn test_huber_loss_rejects_non_positive_delta() {
+    GammaRayAnalyzer::new().set_loss(LossKind::Huber { delta: 0.0 });
+}
+
+#[test]
+fn test_regression_requires_two_distinct_energies() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(100.0, 1.0, 0.01);
+    assert!(analyzer.regress_time_on_energy().is_none());
+    analyzer.add_measurement(100.0, 2.0, 0.01);
+    assert!(analyzer.regress_time_on_energy().is_none());
+    analyzer.add_measurement(200.0, 3.0, 0.01);
+    assert!(analyzer.regress_time_on_energy().is_some());
+}
+
+#[test]
+fn test_large_dataset_matches_serial_reference() {
+    let mut model = liv_model(DispersionOrder::Linear);
+    model.energy_scale = 1.0e6;
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.enable_quantum_gravity_simulation(model.clone());
+    let data: Vec<TimingData> = (0..100_000)
+        .map(|i| {
+            let energy = 0.1 + (i % 1000) as f64 * 0.1;
+            TimingData::new(energy, 10.0 + (i % 7) as f64 * 1e-3, 1e-3)
+                .with_distance(SPEED_OF_LIGHT * 10.0)
+        })
+        .collect();
+    analyzer.add_measurements(&data);
+
+    // Serial reference computed in measurement order
+    let reference: Vec<f64> = data
+        .iter()
+        .map(|m| 10.0 + model.time_delay(m.energy, Some(10.0)))
+        .collect();
+    let expected = analyzer.calculate_expected_arrivals();
+    assert_eq!(expected.len(), reference.len());
+    for ((energy, time), (m, reference_time)) in expected.iter().zip(data.iter().zip(&reference)) {
+        assert_eq!(*energy, m.energy);
+        assert_eq!(*time, *reference_time);
+    }
+
+    let reference_chi_squared = stable_sum(data.iter().zip(&reference).map(|(m, t)| {
+        let deviation = m.arrival_time - t;
+        let weight = 1.0 / (m.error * m.error);
+        weight * deviation * deviation
+    }));
+    let result = analyzer.test_light_speed_constancy();
+    assert_eq!(result.chi_squared, reference_chi_squared);
+    assert_eq!(result.chi_squared, analyzer.test_light_speed_constancy().chi_squared);
+}
+
+#[test]
+fn test_expected_arrivals_are_cached() {
+    let analyzer = graded_anomaly_analyzer();
+    let first = analyzer.calculate_expected_arrivals().as_ptr();
+    let second = analyzer.calculate_expected_arrivals().as_ptr();
+    assert_eq!(first, second);
+
+    let before = analyzer.calculate_expected_arrivals().to_vec();
+    assert_eq!(before, analyzer.calculate_expected_arrivals());
+    analyzer.detect_anomalies();
+    analyzer.test_light_speed_constancy();
+    assert_eq!(analyzer.calculate_expected_arrivals().as_ptr(), first);
+}
+
+#[test]
+fn test_adding_measurement_invalidates_cache() {
+    let mut analyzer = graded_anomaly_analyzer();
+    assert_eq!(analyzer.calculate_expected_arrivals().len(), 4);
+    analyzer.add_measurement(3.0, 1000.0, 1.0);
+    let expected = analyzer.calculate_expected_arrivals();
+    assert_eq!(expected.len(), 5);
+    assert_eq!(expected[4].0, 3.0);
+}
+
+#[test]
+fn test_model_change_invalidates_cache() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement_with_distance(1000.0, 0.0, 0.001, SPEED_OF_LIGHT * 10.0);
+    assert_eq!(analyzer.calculate_expected_arrivals()[0].1, 10.0);
+
+    let mut model = liv_model(DispersionOrder::Linear);
+    model.energy_scale = 1.0e4;
+    analyzer.enable_quantum_gravity_simulation(model);
+    assert!((analyzer.calculate_expected_arrivals()[0].1 - 11.0).abs() < 1e-12);
+}
+
+fn offset_analyzer(offsets: &[(f64, f64)]) -> GammaRayAnalyzer {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for &(energy, offset) in offsets {
+        analyzer.add_measurement_with_distance(energy, travel_time + offset, 1.0, distance);
+    }
+    analyzer
+}
+
+#[test]
+fn test_merge_matches_whole_dataset() {
+    let offsets = [(1.0, 0.5), (2.0, -4.0), (3.0, 1.2), (4.0, 0.1), (5.0, 3.5), (6.0, -0.7)];
+    let whole = offset_analyzer(&offsets);
+    let mut first = offset_analyzer(&offsets[..3]);
+    let second = offset_analyzer(&offsets[3..]);
+    first.test_light_speed_constancy();
+    second.test_light_speed_constancy();
+
+    first.merge(second).unwrap();
+    assert_eq!(first.measurements().len(), offsets.len());
+    assert_eq!(first.calculate_expected_arrivals().len(), offsets.len());
+    let merged = first.test_light_speed_constancy();
+    let expected = whole.test_light_speed_constancy();
+    assert!((merged.chi_squared - expected.chi_squared).abs() < 1e-9);
+    assert!((merged.p_value - expected.p_value).abs() < 1e-12);
+    assert_eq!(merged.anomalies_detected, expected.anomalies_detected);
+    assert_eq!(first.detect_anomalies().len(), whole.detect_anomalies().len());
+}
+
+#[test]
+fn test_merge_rejects_incompatible_configuration() {
+    let mut analyzer = offset_analyzer(&[(1.0, 0.0)]);
+    let mut other = offset_analyzer(&[(2.0, 0.0)]);
+    other.set_cosmology(Cosmology::new(70.0, 0.3, 0.7));
+    assert_eq!(analyzer.merge(other), Err(MergeError::CosmologyMismatch));
+
+    let mut other = offset_analyzer(&[(2.0, 0.0)]);
+    other.enable_quantum_gravity_simulation(liv_model(DispersionOrder::Linear));
+    assert_eq!(analyzer.merge(other), Err(MergeError::ModelMismatch));
+
+    let mut other = offset_analyzer(&[(2.0, 0.0)]);
+    analyzer.add_source("A", SourceGeometry::Distance(1.0e20));
+    other.add_source("A", SourceGeometry::Redshift(1.0));
+    assert_eq!(
+        analyzer.merge(other),
+        Err(MergeError::SourceConflict { id: "A".to_string() })
+    );
+    assert_eq!(analyzer.measurements().len(), 1);
+}
+
+#[test]
+fn test_discan_recovers_injected_lag() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // A 20 ms pulse at t = 100 s dispersed by 0.1 s/GeV over 1-100 GeV
+    let mut rng = StdRng::seed_from_u64(8);
+    let mut analyzer = GammaRayAnalyzer::new();
+    for _ in 0..200 {
+        let energy = rng.gen_range(1.0..100.0);
+        let emission = 100.0 + 0.02 * rng.gen::<f64>();
+        analyzer.add_measurement(energy, emission + 0.1 * energy, 0.01);
+    }
+
+    let result = analyzer.discan_estimate((0.0, 0.2), 81);
+    assert_eq!(result.curve.len(), 81);
+    assert_eq!(result.curve[80].0, 0.2);
+    assert!((result.lag - 0.1).abs() < 0.01, "lag {}", result.lag);
+    assert!(result.curve.iter().all(|&(_, information)| information <= result.information));
+    // Without de-dispersion the light curve is spread over many bins
+    assert!(result.curve[0].1 < result.information - 1.0);
+}
+
+#[test]
+fn test_discan_degenerate_input() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 5.0, 0.1);
+    let result = analyzer.discan_estimate((-1.0, 1.0), 5);
+    assert!(result.lag.is_nan());
+    assert!(result.curve.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "at least two lag steps")]
+fn test_discan_rejects_single_step() {
+    graded_anomaly_analyzer().discan_estimate((0.0, 1.0), 1);
+}
+
+#[test]
+fn test_spectral_lag_bins_photons() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    // Two photons per bin with arrival times 1/2, 3/4 and 5/6 s
+    analyzer.add_measurement(1.0, 1.0, 0.1);
+    analyzer.add_measurement(5.0, 2.0, 0.1);
+    analyzer.add_measurement(10.0, 3.0, 0.1);
+    analyzer.add_measurement(50.0, 4.0, 0.1);
+    analyzer.add_measurement(100.0, 5.0, 0.1);
+    analyzer.add_measurement(1000.0, 6.0, 0.1);
+    // Outside the edges
+    analyzer.add_measurement(0.5, 9.0, 0.1);
+    analyzer.add_measurement(2000.0, 9.0, 0.1);
+
+    let lag = analyzer.spectral_lag(&[1.0, 10.0, 100.0, 1000.0]).unwrap();
+    assert_eq!(lag.dropped, 2);
+    assert_eq!(lag.bins.len(), 3);
+    let counts: Vec<usize> = lag.bins.iter().map(|b| b.count).collect();
+    assert_eq!(counts, vec![2, 2, 2]);
+    let means: Vec<f64> = lag.bins.iter().map(|b| b.mean_arrival_time).collect();
+    assert_eq!(means, vec![1.5, 3.5, 5.5]);
+    assert!((lag.bins[0].mean_arrival_error - 0.1 / 2f64.sqrt()).abs() < 1e-12);
+    assert_eq!(lag.bins[1].energy_low, 10.0);
+    assert_eq!(lag.bins[1].energy_high, 100.0);
+}
+
+#[test]
+fn test_spectral_lag_weights_by_error_and_handles_empty_bins() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(2.0, 0.0, 1.0);
+    analyzer.add_measurement(3.0, 3.0, 2.0);
+    let lag = analyzer.spectral_lag(&[1.0, 10.0, 100.0]).unwrap();
+    assert!((lag.bins[0].mean_arrival_time - 0.6).abs() < 1e-12);
+    assert_eq!(lag.bins[1].count, 0);
+    assert!(lag.bins[1].mean_arrival_time.is_nan());
+}
+
+#[test]
+fn test_spectral_lag_rejects_bad_edges() {
+    let analyzer = graded_anomaly_analyzer();
+    assert!(analyzer.spectral_lag(&[1.0]).is_err());
+    assert!(analyzer.spectral_lag(&[1.0, 10.0, 10.0]).is_err());
+    assert!(analyzer.spectral_lag(&[10.0, 1.0]).is_err());
+    assert!(analyzer.spectral_lag(&[1.0, f64::NAN]).is_err());
+}
+
+#[test]
+fn test_energy_band_summary_per_decade() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    // Decade 1-10 GeV: energies 2 and 4, arrivals 1 and 3 s
+    analyzer.add_measurement(2.0, 1.0, 0.1);
+    analyzer.add_measurement(4.0, 3.0, 0.1);
+    // Decade 10-100 GeV: energies 20, 30 and 40, arrivals 5, 5 and 8 s
+    analyzer.add_measurement(20.0, 5.0, 0.1);
+    analyzer.add_measurement(30.0, 5.0, 0.1);
+    analyzer.add_measurement(40.0, 8.0, 0.1);
+    // Outside the edges
+    analyzer.add_measurement(500.0, 9.0, 0.1);
+
+    let bands = analyzer.energy_band_summary(&[1.0, 10.0, 100.0, 1000.0]);
+    assert_eq!(bands.len(), 3);
+    let counts: Vec<usize> = bands.iter().map(|b| b.count).collect();
+    assert_eq!(counts, vec![2, 3, 1]);
+    assert_eq!((bands[1].energy_low, bands[1].energy_high), (10.0, 100.0));
+    assert!((bands[0].mean_energy - 3.0).abs() < 1e-12);
+    assert!((bands[1].mean_energy - 30.0).abs() < 1e-12);
+    assert!((bands[0].weighted_mean_arrival - 2.0).abs() < 1e-12);
+    assert!((bands[1].weighted_mean_arrival - 6.0).abs() < 1e-12);
+    assert!((bands[0].residual_rms - 1.0).abs() < 1e-12);
+    assert!((bands[1].residual_rms - 2f64.sqrt()).abs() < 1e-12);
+    assert_eq!(bands[2].residual_rms, 0.0);
+
+    let sparse = analyzer.energy_band_summary(&[1000.0, 2000.0]);
+    assert_eq!(sparse[0].count, 0);
+    assert!(sparse[0].mean_energy.is_nan());
+}
+
+#[test]
+#[should_panic(expected = "strictly increasing")]
+fn test_energy_band_summary_rejects_bad_edges() {
+    graded_anomaly_analyzer().energy_band_summary(&[10.0, 1.0]);
+}
+
+#[test]
+fn test_chi_squared_by_bin_sums_to_total() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..30 {
+        let energy = 0.5 * 1.2f64.powi(i);
+        let jitter = if i % 2 == 0 { 0.05 } else { -0.05 };
+        // The 30-100 GeV photons arrive a full second late
+        let arrival = if energy > 30.0 { 1.0 + jitter } else { jitter };
+        analyzer.add_measurement(energy, arrival, 0.1);
+    }
+
+    let bins = analyzer.chi_squared_by_bin(&[0.1, 1.0, 10.0, 30.0, 100.0]);
+    assert_eq!(bins.len(), 4);
+    assert_eq!((bins[3].0, bins[3].1), (30.0, 100.0));
+    let total: f64 = bins.iter().map(|b| b.2).sum();
+    let chi_squared = analyzer.test_light_speed_constancy().chi_squared;
+    assert!((total - chi_squared).abs() < 1e-9 * chi_squared);
+    // The late bin dominates the chi-squared
+    assert!(bins[3].2 > 0.9 * total);
+}
+
+#[test]
+#[should_panic(expected = "bin edges must be strictly increasing")]
+fn test_chi_squared_by_bin_rejects_bad_edges() {
+    graded_anomaly_analyzer().chi_squared_by_bin(&[10.0, 1.0]);
+}
+
+/// 400 photons from sources one light-second apart, arriving in order, whose residuals
+/// follow an AR(1) process with coefficient `phi` and 0.01 s innovations
+fn autoregressive_analyzer(phi: f64, seed: u64) -> GammaRayAnalyzer {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut analyzer = GammaRayAnalyzer::new();
+    let mut residual = 0.0;
+    for i in 0..400 {
+        let innovation: f64 = rng.gen_range(-1.0..1.0) * 3f64.sqrt();
+        residual = phi * residual + 0.01 * innovation;
+        // Residuals stay far below the spacing, so time order is photon order
+        let travel_time = 1000.0 + i as f64;
+        let distance = travel_time * SPEED_OF_LIGHT;
+        analyzer.add_measurement_with_distance(1.0, travel_time + residual, 0.01, distance);
+    }
+    analyzer
+}
+
+#[test]
+fn test_durbin_watson_flags_serially_correlated_residuals() {
+    let correlated = autoregressive_analyzer(0.9, 21);
+    let independent = autoregressive_analyzer(0.0, 21);
+    let dw = correlated.durbin_watson();
+    assert!(dw < 0.5, "{}", dw);
+    let dw_independent = independent.durbin_watson();
+    assert!((dw_independent - 2.0).abs() < 0.3, "{}", dw_independent);
+
+    let acf = correlated.residual_autocorrelation(3);
+    assert_eq!(acf.len(), 4);
+    assert!((acf[0] - 1.0).abs() < 1e-12);
+    assert!(acf[1] > 0.75 && acf[1] > acf[2] && acf[2] > acf[3], "{:?}", acf);
+    assert!((dw - 2.0 * (1.0 - acf[1])).abs() < 0.05, "{} vs {:?}", dw, acf);
+    assert!(independent.residual_autocorrelation(1)[1].abs() < 0.15);
+}
+
+#[test]
+fn test_residual_autocorrelation_degenerate_input() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    assert!(analyzer.durbin_watson().is_nan());
+    analyzer.add_measurement(1.0, 0.0, 0.1);
+    analyzer.add_measurement(1.0, 1.0, 0.1);
+    assert!(analyzer.residual_autocorrelation(2)[2].is_nan());
+    analyzer.set_reference_time(0.5);
+    // Two centred residuals always alternate in sign
+    assert_eq!(analyzer.durbin_watson(), 2.0);
+    assert_eq!(analyzer.residual_autocorrelation(1), vec![1.0, -0.5]);
+}
+
+#[test]
+fn test_log_bin_edges_have_constant_ratio() {
+    let edges = log_bin_edges(0.1, 1000.0, 8);
+    assert_eq!(edges.len(), 9);
+    assert_eq!((edges[0], edges[8]), (0.1, 1000.0));
+    let ratio = 10f64.powf(0.5);
+    for pair in edges.windows(2) {
+        assert!((pair[1] / pair[0] - ratio).abs() < 1e-12, "{:?}", edges);
+    }
+    assert_eq!(log_bin_edges(2.0, 5.0, 1), vec![2.0, 5.0]);
+
+    // The edges feed straight into the binned summaries
+    let bands = graded_anomaly_analyzer().energy_band_summary(&log_bin_edges(0.1, 10.0, 4));
+    assert_eq!(bands.iter().map(|b| b.count).sum::<usize>(), 4);
+}
+
+#[test]
+#[should_panic(expected = "energy bounds must satisfy 0 < e_min < e_max")]
+fn test_log_bin_edges_rejects_non_positive_minimum() {
+    log_bin_edges(0.0, 10.0, 4);
+}
+
+#[test]
+#[should_panic(expected = "energy bounds must satisfy 0 < e_min < e_max")]
+fn test_log_bin_edges_rejects_inverted_range() {
+    log_bin_edges(10.0, 1.0, 4);
+}
+
+#[test]
+#[should_panic(expected = "bin count must be at least 1")]
+fn test_log_bin_edges_rejects_zero_bins() {
+    log_bin_edges(1.0, 10.0, 0);
+}
+
+#[test]
+fn test_cross_correlation_recovers_injected_lag() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    // Asymmetric pulse; times are exact binary fractions so binning is unambiguous
+    let profile = [1, 3, 7, 12, 9, 4, 2, 6, 3, 1];
+    for (bin, &count) in profile.iter().enumerate() {
+        let t = 2.0 + bin as f64 * 0.25 + 0.125;
+        for _ in 0..count {
+            analyzer.add_measurement(5.0, t, 0.01);
+            analyzer.add_measurement(500.0, t + 1.0, 0.01);
+        }
+    }
+
+    let result = analyzer.cross_correlation_lag((1.0, 10.0), (100.0, 1000.0), 0.25, 2.0).unwrap();
+    assert_eq!(result.lag, 1.0);
+    assert!(result.correlation > 0.5);
+    assert_eq!(result.low_count, result.high_count);
+
+    let reversed = analyzer.cross_correlation_lag((100.0, 1000.0), (1.0, 10.0), 0.25, 2.0).unwrap();
+    assert_eq!(reversed.lag, -1.0);
+}
+
+#[test]
+fn test_cross_correlation_handles_empty_band() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(5.0, 1.0, 0.01);
+    analyzer.add_measurement(5.0, 2.0, 0.01);
+    let result = analyzer.cross_correlation_lag((1.0, 10.0), (100.0, 1000.0), 0.25, 2.0).unwrap();
+    assert_eq!(result.high_count, 0);
+    assert_eq!(result.lag, 0.0);
+    assert_eq!(result.correlation, 0.0);
+}
+
+#[test]
+#[should_panic(expected = "bin width")]
+fn test_cross_correlation_rejects_zero_bin_width() {
+    let _ = graded_anomaly_analyzer().cross_correlation_lag((1.0, 10.0), (100.0, 1000.0), 0.0, 2.0);
+}
+
+#[test]
+fn test_cross_correlation_rejects_too_many_bins() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(5.0, 1.0, 0.01);
+    analyzer.add_measurement(500.0, 2.0, 0.01);
+    // One stray photon a year away from the burst
+    analyzer.add_measurement(500.0, 3.2e7, 0.01);
+    let error = analyzer.cross_correlation_lag((1.0, 10.0), (100.0, 1000.0), 1e-3, 2.0);
+    assert!(error.unwrap_err().contains("maximum"));
+}
+
+#[test]
+fn test_binned_light_curve_counts_every_photon() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for &t in &[10.0, 10.4, 11.2, 13.9, 14.0] {
+        analyzer.add_measurement(1.0, t, 0.01);
+    }
+    let curve = analyzer.binned_light_curve(1.0).unwrap();
+    let counts: Vec<usize> = curve.iter().map(|&(_, count)| count).collect();
+    assert_eq!(counts, vec![2, 1, 0, 1, 1]);
+    assert_eq!(curve[0].0, 10.0);
+    assert_eq!(curve[3].0, 13.0);
+    assert!(GammaRayAnalyzer::new().binned_light_curve(1.0).unwrap().is_empty());
+}
+
+#[test]
+fn test_binned_light_curve_rejects_too_many_bins() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 0.0, 0.01);
+    analyzer.add_measurement(1.0, 3.2e7, 0.01);
+    let error = analyzer.binned_light_curve(1e-3).unwrap_err();
+    assert!(error.contains("maximum"));
+    assert_eq!(analyzer.binned_light_curve(10.0).unwrap().len(), 3_200_001);
+}
+
+#[test]
+fn test_smoothing_spiky_light_curve_reduces_variance() {
+    let spiky: Vec<f64> = (0..40).map(|i| if i % 4 == 0 { 12.0 } else { 2.0 }).collect();
+    let smoothed = GammaRayAnalyzer::smooth_light_curve(&spiky, 5);
+    assert_eq!(smoothed.len(), spiky.len());
+
+    let variance = |curve: &[f64]| {
+        let mean = curve.iter().sum::<f64>() / curve.len() as f64;
+        curve.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / curve.len() as f64
+    };
+    assert!(variance(&smoothed) < 0.25 * variance(&spiky));
+
+    let total: f64 = spiky.iter().sum();
+    assert!((smoothed.iter().sum::<f64>() - total).abs() / total < 0.05);
+}
+
+#[test]
+#[should_panic(expected = "must be odd")]
+fn test_smoothing_rejects_even_window() {
+    GammaRayAnalyzer::smooth_light_curve(&[1.0, 2.0, 3.0, 4.0, 5.0], 2);
+}
+
+#[test]
+#[should_panic(expected = "smaller than the light curve length")]
+fn test_smoothing_rejects_oversized_window() {
+    GammaRayAnalyzer::smooth_light_curve(&[1.0, 2.0, 3.0], 3);
+}
+
+#[test]
+fn test_find_peaks_recovers_double_pulse() {
+    // Two Gaussian pulses at bins 10 and 30 on a flat background, plus a small bump at 20
+    let profile: Vec<f64> = (0..40)
+        .map(|i| {
+            let x = i as f64;
+            2.0 + 20.0 * (-(x - 10.0).powi(2) / 8.0).exp()
+                + 12.0 * (-(x - 30.0).powi(2) / 8.0).exp()
+                + if i == 20 { 1.0 } else { 0.0 }
+        })
+        .collect();
+    assert_eq!(GammaRayAnalyzer::find_peaks(&profile, 5.0), vec![10, 30]);
+    assert_eq!(GammaRayAnalyzer::find_peaks(&profile, 0.5), vec![10, 20, 30]);
+}
+
+#[test]
+fn test_find_peaks_plateaus_and_edges() {
+    // Plateau peak at bins 2-4, rising edge at the end is not a peak
+    let profile = [0.0, 1.0, 5.0, 5.0, 5.0, 1.0, 2.0, 9.0];
+    assert_eq!(GammaRayAnalyzer::find_peaks(&profile, 1.0), vec![3]);
+    // A shoulder that rises again is not a peak
+    assert_eq!(GammaRayAnalyzer::find_peaks(&[0.0, 3.0, 3.0, 4.0, 0.0], 0.0), vec![3]);
+    assert!(GammaRayAnalyzer::find_peaks(&[1.0, 2.0], 0.0).is_empty());
+}
+
+#[test]
+fn test_validate_accepts_physical_measurement() {
+    let measurement = TimingData::new(10.0, 1.5, 0.01).with_redshift(0.5);
+    assert_eq!(measurement.validate(), Ok(()));
+
+    let mut analyzer = GammaRayAnalyzer::new();
+    assert!(analyzer.try_add_measurement(10.0, 1.5, 0.01).is_ok());
+    assert_eq!(analyzer.len(), 1);
+}
+
+#[test]
+fn test_validate_rejects_non_positive_energy() {
+    assert_eq!(
+        TimingData::new(0.0, 1.0, 0.01).validate(),
+        Err(TimingDataError::NonPositiveEnergy(0.0))
+    );
+    assert_eq!(
+        TimingData::new(-1.0, 1.0, 0.01).validate(),
+        Err(TimingDataError::NonPositiveEnergy(-1.0))
+    );
+}
+
+#[test]
+fn test_validate_rejects_non_positive_error() {
+    assert_eq!(
+        TimingData::new(1.0, 1.0, 0.0).validate(),
+        Err(TimingDataError::NonPositiveError(0.0))
+    );
+    assert_eq!(
+        TimingData::new(1.0, 1.0, -0.5).validate(),
+        Err(TimingDataError::NonPositiveError(-0.5))
+    );
+}
+
+#[test]
+fn test_validate_rejects_non_finite_fields() {
+    let field = |m: TimingData| match m.validate() {
+        Err(TimingDataError::NonFinite { field, .. }) => field,
+        other => panic!("expected non-finite rejection, got {:?}", other),
+    };
+    assert_eq!(field(TimingData::new(f64::NAN, 1.0, 0.01)), "energy");
+    assert_eq!(field(TimingData::new(1.0, f64::NAN, 0.01)), "arrival_time");
+    assert_eq!(field(TimingData::new(1.0, 1.0, f64::INFINITY)), "error");
+    assert_eq!(field(TimingData::new(1.0, 1.0, 0.01).with_distance(f64::INFINITY)), "distance_m");
+    assert_eq!(field(TimingData::new(1.0, 1.0, 0.01).with_redshift(f64::NAN)), "redshift");
+}
+
+#[test]
+fn test_try_add_measurement_leaves_analyzer_unchanged_on_rejection() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    let err = analyzer.try_add_measurement(1.0, 1.0, 0.0).unwrap_err();
+    assert_eq!(err.to_string(), "measurement error must be positive, got 0");
+    assert!(analyzer.is_empty());
+}
+
+#[test]
+fn test_energy_scale_lower_bound_is_below_point_estimate() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for (energy, time) in [(1.0, 0.011), (10.0, 0.098), (100.0, 1.003)] {
+        analyzer.add_measurement(energy, time, 0.01);
+    }
+    let fit = analyzer.fit_liv_slope();
+    let travel_time = 1.0e17;
+    let bound = fit.energy_scale_lower_bound(travel_time, 0.95).unwrap();
+    assert!(bound > 0.0);
+    assert!(bound < travel_time / fit.slope);
+    assert!(fit.energy_scale_lower_bound(0.0, 0.95).is_none());
+    assert!(fit.energy_scale_lower_bound(travel_time, 1.0).is_none());
+}
+
+#[test]
+fn test_weighted_mean_and_variance() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    assert_eq!(analyzer.weighted_mean_arrival(), None);
+    assert_eq!(analyzer.weighted_arrival_variance(), None);
+
+    // Weights 4 and 1: mean = (4·1 + 1·6) / 5 = 2, variance = (4·1 + 1·16) / 5 = 4
+    analyzer.add_measurement(1.0, 1.0, 0.5);
+    analyzer.add_measurement(2.0, 6.0, 1.0);
+    assert!((analyzer.weighted_mean_arrival().unwrap() - 2.0).abs() < 1e-12);
+    assert!((analyzer.weighted_arrival_variance().unwrap() - 4.0).abs() < 1e-12);
+
+    // Zero-error points carry no weight
+    analyzer.add_measurement(3.0, 100.0, 0.0);
+    assert!((analyzer.weighted_mean_arrival().unwrap() - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_weighted_mean_none_without_usable_weight() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 1.0, 0.0);
+    analyzer.add_measurement(1.0, 2.0, f64::INFINITY);
+    assert_eq!(analyzer.weighted_mean_arrival(), None);
+    assert_eq!(analyzer.weighted_arrival_variance(), None);
+}
+
+#[test]
+fn test_energy_time_correlation_linear_delay() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 1..=10 {
+        let energy = i as f64 * 10.0;
+        analyzer.add_measurement(energy, 2.0e-3 * energy, 0.01);
+    }
+    assert!((analyzer.energy_time_correlation().unwrap() - 1.0).abs() < 1e-12);
+
+    let mut advanced = GammaRayAnalyzer::new();
+    for i in 1..=10 {
+        let energy = i as f64 * 10.0;
+        advanced.add_measurement_with_distance(energy, 1.0e9 / SPEED_OF_LIGHT - 1.0e-3 * energy, 0.01, 1.0e9);
+    }
+    assert!((advanced.energy_time_correlation().unwrap() + 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_energy_time_correlation_uncorrelated() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    // Residuals symmetric about the energy midpoint: covariance is exactly zero
+    for (energy, time) in [(1.0, 0.0), (2.0, 1.0), (3.0, 0.0), (4.0, 1.0), (5.0, 0.0)] {
+        analyzer.add_measurement(energy, time, 0.01);
+    }
+    assert!(analyzer.energy_time_correlation().unwrap().abs() < 1e-12);
+}
+
+#[test]
+fn test_energy_time_correlation_degenerate() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 1.0, 0.01);
+    assert_eq!(analyzer.energy_time_correlation(), None);
+    analyzer.add_measurement(1.0, 2.0, 0.01);
+    assert_eq!(analyzer.energy_time_correlation(), None);
+}
+
+#[test]
+fn test_remove_measurement_from_middle() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 0.1, 0.01);
+    analyzer.add_measurement(2.0, 0.2, 0.01);
+    analyzer.add_measurement(3.0, 0.3, 0.01);
+    assert_eq!(analyzer.calculate_expected_arrivals().len(), 3);
+
+    let removed = analyzer.remove_measurement(1).unwrap();
+    assert_eq!(removed.energy, 2.0);
+    assert_eq!(removed.arrival_time, 0.2);
+    assert_eq!(analyzer.len(), 2);
+    assert_eq!(analyzer.measurements()[1].energy, 3.0);
+    // The cached expectations follow the removal
+    assert_eq!(analyzer.calculate_expected_arrivals().len(), 2);
+
+    assert!(analyzer.remove_measurement(5).is_none());
+    assert_eq!(analyzer.len(), 2);
+}
+
+#[test]
+fn test_clear_keeps_configuration() {
+    let mut analyzer = graded_anomaly_analyzer();
+    analyzer.set_sensitivity_threshold(1e-9);
+    analyzer.clear();
+    assert!(analyzer.is_empty());
+    assert!(analyzer.calculate_expected_arrivals().is_empty());
+    assert_eq!(analyzer.sensitivity_threshold(), 1e-9);
+}
+
+#[test]
+fn test_deduplicate_times_separates_ties_within_jitter() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..40 {
+        // Four distinct recorded times, ten photons each
+        analyzer.add_measurement(1.0 + i as f64, 1000.0 + (i % 4) as f64, 0.01);
+    }
+    let recorded: Vec<f64> = analyzer.measurements().iter().map(|m| m.arrival_time).collect();
+
+    let jitter = 1e-3;
+    assert_eq!(analyzer.deduplicate_times(jitter, 5), 36);
+    let times: Vec<f64> = analyzer.measurements().iter().map(|m| m.arrival_time).collect();
+    for (time, original) in times.iter().zip(&recorded) {
+        assert!((time - original).abs() < jitter / 2.0, "{} from {}", time, original);
+    }
+    let mut sorted = times.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+    // The first photon at each time is untouched
+    assert_eq!(times[..4], recorded[..4]);
+
+    assert_eq!(analyzer.deduplicate_times(jitter, 5), 0);
+}
+
+#[test]
+fn test_deduplicate_times_is_reproducible() {
+    let build = || {
+        let mut analyzer = GammaRayAnalyzer::new();
+        for energy in 1..=5 {
+            analyzer.add_measurement(energy as f64, 10.0, 0.1);
+        }
+        analyzer
+    };
+    let (mut first, mut second) = (build(), build());
+    first.deduplicate_times(0.01, 42);
+    second.deduplicate_times(0.01, 42);
+    let times = |a: &GammaRayAnalyzer| -> Vec<f64> {
+        a.measurements().iter().map(|m| m.arrival_time).collect()
+    };
+    assert_eq!(times(&first), times(&second));
+    assert_ne!(times(&first)[1], 10.0);
+}
+
+#[test]
+#[should_panic(expected = "jitter must be positive and finite")]
+fn test_deduplicate_times_rejects_zero_jitter() {
+    graded_anomaly_analyzer().deduplicate_times(0.0, 0);
+}
+
+#[test]
+fn test_sigma_clip_removes_obvious_outlier() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for (i, scatter) in [0.01, -0.01, 0.02, -0.02, 0.0, 0.01, -0.01, 0.015, -0.015, 0.0]
+        .iter()
+        .enumerate()
+    {
+        analyzer.add_measurement(1.0 + i as f64, 1.0 + scatter, 0.01);
+    }
+    analyzer.add_measurement(50.0, 5.0, 0.01);
+
+    assert_eq!(analyzer.sigma_clip(3.0), 1);
+    assert_eq!(analyzer.len(), 10);
+    assert!(analyzer.measurements().iter().all(|m| m.energy != 50.0));
+    // Already clean data is stable
+    assert_eq!(analyzer.sigma_clip(3.0), 0);
+}
+
+#[test]
+fn test_sigma_clip_keeps_at_least_three() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 0.0, 0.01);
+    analyzer.add_measurement(2.0, 0.0, 0.01);
+    analyzer.add_measurement(3.0, 10.0, 0.01);
+    // Clipping at a tiny threshold would leave two points, so nothing is removed
+    assert_eq!(analyzer.sigma_clip(0.1), 0);
+    assert_eq!(analyzer.len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "clipping sigma")]
+fn test_sigma_clip_rejects_non_positive_sigma() {
+    graded_anomaly_analyzer().sigma_clip(0.0);
+}
+
+/// Two photons 1.6σ late give χ² = 5.12 with one degree of freedom, p ≈ 0.024
+fn marginal_analyzer() -> GammaRayAnalyzer {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement_with_distance(1.0, travel_time + 1.6, 1.0, distance);
+    analyzer.add_measurement_with_distance(2.0, travel_time + 1.6, 1.0, distance);
+    analyzer
+}
+
+#[test]
+fn test_significance_level_controls_validity() {
+    let mut analyzer = marginal_analyzer();
+    let p_value = analyzer.test_light_speed_constancy().p_value;
+    assert!(p_value > 0.001 && p_value < 0.1, "{}", p_value);
+
+    analyzer.set_significance_level(1.0 - 0.90);
+    assert!(!analyzer.test_light_speed_constancy().is_valid);
+    analyzer.set_significance_level(1.0 - 0.999);
+    assert!(analyzer.test_light_speed_constancy().is_valid);
+    assert!((analyzer.significance_level() - 0.001).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "significance level must be in (0, 1)")]
+fn test_significance_level_rejects_out_of_range() {
+    GammaRayAnalyzer::new().set_significance_level(1.0);
+}
+
+#[test]
+fn test_config_round_trip_reproduces_analysis() {
+    let mut analyzer = graded_anomaly_analyzer();
+    analyzer.set_anomaly_sigma(2.0);
+    analyzer.set_significance_level(0.01);
+    analyzer.set_sensitivity_threshold(1e-9);
+    analyzer.set_cosmology(Cosmology::planck_2018());
+
+    let json = serde_json::to_string(&analyzer.config()).unwrap();
+    let mut restored = GammaRayAnalyzer::from_config(serde_json::from_str(&json).unwrap());
+    assert!(restored.is_empty());
+    restored.add_measurements(analyzer.measurements());
+
+    assert_eq!(serde_json::to_string(&restored.config()).unwrap(), json);
+    let expected = analyzer.test_light_speed_constancy();
+    let actual = restored.test_light_speed_constancy();
+    assert_eq!(actual.chi_squared, expected.chi_squared);
+    assert_eq!(actual.is_valid, expected.is_valid);
+    assert_eq!(restored.detect_anomalies().len(), analyzer.detect_anomalies().len());
+    assert_eq!(restored.detect_anomalies().len(), 3);
+}
+
+#[test]
+fn test_residual_quantiles_of_uniform_offsets() {
+    // Residuals 0, 1, ..., 100 s after the light-travel time, added out of order
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..=100 {
+        let offset = ((i * 37) % 101) as f64;
+        analyzer.add_measurement_with_distance(1.0 + i as f64, travel_time + offset, 1.0, distance);
+    }
+    let quantiles = analyzer.residual_quantiles(&[0.5, 0.25, 0.75, 0.0, 1.0, 0.125]);
+    let expected = [50.0, 25.0, 75.0, 0.0, 100.0, 12.5];
+    for (q, e) in quantiles.iter().zip(expected) {
+        assert!((q - e).abs() < 1e-6, "{:?}", quantiles);
+    }
+    assert!(GammaRayAnalyzer::new().residual_quantiles(&[0.5])[0].is_nan());
+}
+
+#[test]
+#[should_panic(expected = "quantiles must be in [0, 1]")]
+fn test_residual_quantiles_reject_out_of_range() {
+    graded_anomaly_analyzer().residual_quantiles(&[0.5, 1.5]);
+}
+
+/// 21 photons whose deviations step from -1 to 1 s, plus a late outlier when `outlier`
+/// is set; the 0.01 s errors understate the scatter a hundredfold
+fn scattered_analyzer(outlier: Option<f64>) -> GammaRayAnalyzer {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..=20 {
+        let arrival = travel_time + (i as f64 - 10.0) / 10.0;
+        analyzer.add_measurement_with_distance(1.0 + i as f64, arrival, 0.01, distance);
+    }
+    if let Some(offset) = outlier {
+        analyzer.add_measurement_with_distance(100.0, travel_time + offset, 0.01, distance);
+    }
+    analyzer
+}
+
+#[test]
+fn test_residual_mad_ignores_outlier() {
+    let standard_deviation = |analyzer: &GammaRayAnalyzer| {
+        let deviations: Vec<f64> = analyzer
+            .measurements()
+            .iter()
+            .zip(analyzer.calculate_expected_arrivals())
+            .map(|(m, expected)| m.arrival_time - expected.1)
+            .collect();
+        let mean = deviations.iter().sum::<f64>() / deviations.len() as f64;
+        let variance = deviations.iter().map(|d| (d - mean).powi(2)).sum::<f64>()
+            / (deviations.len() - 1) as f64;
+        variance.sqrt()
+    };
+    let clean = scattered_analyzer(None);
+    let contaminated = scattered_analyzer(Some(1000.0));
+
+    assert!(clean.weighted_median_residual().unwrap().abs() < 1e-9);
+    assert!((clean.residual_mad().unwrap() - 0.5).abs() < 1e-9);
+    // Even count: the median is the midpoint of the two central deviations, 0 and 0.1
+    assert!((contaminated.weighted_median_residual().unwrap() - 0.05).abs() < 1e-9);
+    let mad_shift = contaminated.residual_mad().unwrap() - clean.residual_mad().unwrap();
+    assert!(mad_shift.abs() < 0.1, "{}", mad_shift);
+    assert!(standard_deviation(&contaminated) > 100.0 * standard_deviation(&clean));
+
+    assert_eq!(GammaRayAnalyzer::new().weighted_median_residual(), None);
+    assert_eq!(GammaRayAnalyzer::new().residual_mad(), None);
+}
+
+#[test]
+fn test_detect_anomalies_robust_flags_only_outlier() {
+    let analyzer = scattered_analyzer(Some(1000.0));
+    // The understated errors make the Gaussian detector flag most photons
+    assert!(analyzer.detect_anomalies().len() > 10);
+    let anomalies = analyzer.detect_anomalies_robust();
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0].energy, 100.0);
+    assert!(anomalies[0].significance > 1000.0);
+
+    assert!(scattered_analyzer(None).detect_anomalies_robust().is_empty());
+}
+
+#[test]
+fn test_detect_anomalies_robust_falls_back_to_errors_when_mad_is_zero() {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..10 {
+        let late = if i < 6 { 0.0 } else { 0.001 };
+        analyzer.add_measurement_with_distance(1.0 + i as f64, travel_time + late, 0.1, distance);
+    }
+    assert_eq!(analyzer.residual_mad(), Some(0.0));
+    assert!(analyzer.detect_anomalies_robust().is_empty());
+
+    // A photon 1 s late is still flagged, at ten of its own errors
+    analyzer.add_measurement_with_distance(20.0, travel_time + 1.0, 0.1, distance);
+    let anomalies = analyzer.detect_anomalies_robust();
+    assert_eq!(anomalies.len(), 1);
+    assert!((anomalies[0].significance - 10.0).abs() < 1e-3, "{:?}", anomalies[0]);
+}
+
+/// 95 photons with deviations within ±0.9 s and five tail photons at 1000 GeV and above
+/// deviating by 20 s or more, all with 0.25 s errors
+fn heavy_tailed_analyzer() -> GammaRayAnalyzer {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..95 {
+        let arrival = travel_time + ((i % 19) as f64 - 9.0) / 10.0;
+        analyzer.add_measurement_with_distance(1.0 + i as f64, arrival, 0.25, distance);
+    }
+    for (k, deviation) in [20.0, -22.0, 25.0, -27.0, 30.0].iter().enumerate() {
+        let energy = 1000.0 + k as f64;
+        analyzer.add_measurement_with_distance(energy, travel_time + deviation, 0.25, distance);
+    }
+    analyzer
+}
+
+#[test]
+fn test_detect_anomalies_percentile_flags_tail_photons() {
+    let analyzer = heavy_tailed_analyzer();
+    let tail_energies = vec![1000.0, 1001.0, 1002.0, 1003.0, 1004.0];
+
+    // The 3σ cut also flags the 0.8 and 0.9 s deviations of the bulk
+    let sigma: Vec<f64> = analyzer.detect_anomalies().iter().map(|a| a.energy).collect();
+    assert!(sigma.len() > tail_energies.len(), "{:?}", sigma);
+    assert!(tail_energies.iter().all(|e| sigma.contains(e)));
+
+    // The 95th percentile of |deviation| falls between the bulk and the tail
+    let anomalies = analyzer.detect_anomalies_percentile(95.0);
+    let flagged: Vec<f64> = anomalies.iter().map(|a| a.energy).collect();
+    assert_eq!(flagged, tail_energies);
+    assert_eq!(anomalies[1].kind, AnomalyKind::EarlyArrival);
+    assert!((anomalies[4].significance - 120.0).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "percentile must be in (0, 100)")]
+fn test_detect_anomalies_percentile_rejects_zero() {
+    heavy_tailed_analyzer().detect_anomalies_percentile(0.0);
+}
+
+#[test]
+#[should_panic(expected = "percentile must be in (0, 100)")]
+fn test_detect_anomalies_percentile_rejects_hundred() {
+    heavy_tailed_analyzer().detect_anomalies_percentile(100.0);
+}
+
+#[test]
+fn test_mixture_recovers_two_separated_populations() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut rng = StdRng::seed_from_u64(21);
+    let mut gaussian = |mean: f64, sigma: f64| {
+        let u1: f64 = 1.0 - rng.gen::<f64>();
+        let u2: f64 = rng.gen();
+        mean + sigma * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    };
+    // 300 prompt photons near 0 s and 100 afterglow photons near 20 s
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..400 {
+        let offset = if i % 4 == 0 { gaussian(20.0, 2.0) } else { gaussian(0.0, 1.0) };
+        analyzer.add_measurement_with_distance(1.0, travel_time + offset, 1.0, distance);
+    }
+
+    let mixture = analyzer.fit_two_gaussian_mixture().unwrap();
+    assert!(mixture.means[0].abs() < 0.3, "{:?}", mixture);
+    assert!((mixture.means[1] - 20.0).abs() < 0.6, "{:?}", mixture);
+    assert!((mixture.weights[0] - 0.75).abs() < 0.05, "{:?}", mixture);
+    assert!((mixture.weights[0] + mixture.weights[1] - 1.0).abs() < 1e-9);
+    assert!((mixture.variances[0].sqrt() - 1.0).abs() < 0.2, "{:?}", mixture);
+    assert!((mixture.variances[1].sqrt() - 2.0).abs() < 0.5, "{:?}", mixture);
+}
+
+#[test]
+fn test_mixture_needs_enough_spread_data() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for _ in 0..3 {
+        analyzer.add_measurement(1.0, 5.0, 1.0);
+    }
+    assert!(analyzer.fit_two_gaussian_mixture().is_none());
+    analyzer.add_measurement(1.0, 5.0, 1.0);
+    // Identical residuals collapse both components to zero variance
+    assert!(analyzer.fit_two_gaussian_mixture().is_none());
+}
+
+#[test]
+fn test_asymmetric_errors_weight_by_residual_side() {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let late = TimingData::new(1.0, travel_time + 2.0, 1.0).with_distance(distance);
+    let early = TimingData::new(2.0, travel_time - 2.0, 1.0).with_distance(distance);
+
+    let chi_squared = |measurements: &[TimingData]| {
+        let mut analyzer = GammaRayAnalyzer::new();
+        analyzer.add_measurements(measurements);
+        analyzer.test_light_speed_constancy().chi_squared
+    };
+    let symmetric = chi_squared(&[late.clone(), early.clone()]);
+    assert!((symmetric - 8.0).abs() < 1e-6, "{}", symmetric);
+
+    // The late photon is judged by its lower error, the early one by its upper error
+    let asymmetric = chi_squared(&[
+        late.clone().with_asymmetric_errors(2.0, 0.5),
+        early.clone().with_asymmetric_errors(0.5, 4.0),
+    ]);
+    assert!((asymmetric - (1.0 + 0.25)).abs() < 1e-6, "{}", asymmetric);
+
+    let swapped = chi_squared(&[
+        late.with_asymmetric_errors(0.5, 2.0),
+        early.with_asymmetric_errors(4.0, 0.5),
+    ]);
+    assert!((swapped - (16.0 + 16.0)).abs() < 1e-6, "{}", swapped);
+}
+
+#[test]
+fn test_down_weighted_photon_contributes_proportionally_less() {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let late = TimingData::new(1.0, travel_time + 2.0, 1.0).with_distance(distance);
+    let early = TimingData::new(2.0, travel_time - 1.0, 1.0).with_distance(distance);
+    let result = |data: &[TimingData]| {
+        let mut analyzer = GammaRayAnalyzer::new();
+        analyzer.add_measurements(data);
+        analyzer.test_light_speed_constancy()
+    };
+
+    let unweighted = result(&[late.clone(), early.clone()]);
+    assert!((unweighted.chi_squared - 5.0).abs() < 1e-6);
+    assert!((unweighted.deviation_estimate.unwrap() - 0.5).abs() < 1e-6);
+
+    // Half weight halves the early photon's χ² term and its share of the weighted mean
+    let weighted = result(&[late.clone(), early.clone().with_weight(0.5)]);
+    assert!((weighted.chi_squared - 4.5).abs() < 1e-6, "{}", weighted.chi_squared);
+    assert!((weighted.deviation_estimate.unwrap() - 1.0).abs() < 1e-6);
+    assert!((weighted.deviation_error.unwrap() - 1.0 / 1.5f64.sqrt()).abs() < 1e-9);
+
+    // An explicit unit weight matches the unweighted result
+    let unit = result(&[late.with_weight(1.0), early]);
+    assert_eq!(unit.chi_squared, unweighted.chi_squared);
+}
+
+#[test]
+fn test_validate_rejects_non_positive_weight() {
+    let measurement = TimingData::new(1.0, 1.0, 0.1).with_weight(0.0);
+    assert_eq!(measurement.validate(), Err(TimingDataError::NonPositiveWeight(0.0)));
+    let measurement = TimingData::new(1.0, 1.0, 0.1).with_weight(f64::INFINITY);
+    assert!(matches!(
+        measurement.validate(),
+        Err(TimingDataError::NonFinite { field: "weight", .. })
+    ));
+}
+
+#[test]
+fn test_validate_rejects_non_positive_asymmetric_error() {
+    let measurement = TimingData::new(1.0, 1.0, 0.1).with_asymmetric_errors(0.1, 0.0);
+    assert_eq!(measurement.validate(), Err(TimingDataError::NonPositiveError(0.0)));
+    let measurement = TimingData::new(1.0, 1.0, 0.1).with_asymmetric_errors(f64::NAN, 0.1);
+    assert!(matches!(
+        measurement.validate(),
+        Err(TimingDataError::NonFinite { field: "error_low", .. })
+    ));
+}
+
+#[test]
+fn test_systematic_error_floor_reduces_chi_squared() {
+    let mut analyzer = marginal_analyzer();
+    let statistical = analyzer.test_light_speed_constancy();
+    let fit_error = analyzer.fit_liv_slope().slope_error;
+    assert!((statistical.chi_squared - 5.12).abs() < 1e-6);
+
+    // σ_sys = 1 s doubles every variance, halving χ²
+    analyzer.set_systematic_error(1.0);
+    let with_floor = analyzer.test_light_speed_constancy();
+    assert!((with_floor.chi_squared - 2.56).abs() < 1e-6, "{}", with_floor.chi_squared);
+    assert!(with_floor.p_value > statistical.p_value);
+    assert!((analyzer.fit_liv_slope().slope_error / fit_error - 2f64.sqrt()).abs() < 1e-9);
+
+    analyzer.set_systematic_error(0.0);
+    assert_eq!(analyzer.test_light_speed_constancy().chi_squared, statistical.chi_squared);
+}
+
+#[test]
+fn test_systematic_error_suppresses_anomalies() {
+    let mut analyzer = graded_anomaly_analyzer();
+    assert_eq!(analyzer.detect_anomalies().len(), 2);
+    analyzer.set_systematic_error(2.0);
+    // Significances fall by √5, leaving the largest (6.25σ) at 2.8σ
+    assert_eq!(analyzer.detect_anomalies().len(), 0);
+    assert_eq!(analyzer.config().systematic_error, 2.0);
+}
+
+#[test]
+#[should_panic(expected = "systematic error must be non-negative and finite")]
+fn test_systematic_error_rejects_negative() {
+    GammaRayAnalyzer::new().set_systematic_error(-1.0);
+}
+
+#[test]
+fn test_filter_time_window_keeps_in_window_photons() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for (i, &t) in [5.0, 10.0, 12.5, 20.0, 20.5, 30.0].iter().enumerate() {
+        analyzer.add_measurement(1.0 + i as f64, t, 0.1);
+    }
+    let before = analyzer.weighted_mean_arrival().unwrap();
+
+    assert_eq!(analyzer.filter_time_window(10.0, 20.0), 3);
+    let times: Vec<f64> = analyzer.measurements().iter().map(|m| m.arrival_time).collect();
+    assert_eq!(times, vec![10.0, 12.5, 20.0]);
+    assert_ne!(analyzer.weighted_mean_arrival().unwrap(), before);
+    assert_eq!(analyzer.filter_time_window(0.0, 100.0), 3);
+    assert_eq!(analyzer.filter_time_window(50.0, 60.0), 0);
+    assert!(analyzer.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "time window start must be before its end")]
+fn test_filter_time_window_rejects_empty_window() {
+    graded_anomaly_analyzer().filter_time_window(10.0, 10.0);
+}
+
+#[test]
+fn test_filter_energy_range_keeps_band() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    for (i, &energy) in [0.1, 1.0, 5.0, 10.0, 50.0, 100.0, 1000.0].iter().enumerate() {
+        analyzer.add_measurement(energy, i as f64, 0.1);
+    }
+    assert_eq!(analyzer.filter_energy_range(1.0, 100.0), 5);
+    let energies: Vec<f64> = analyzer.measurements().iter().map(|m| m.energy).collect();
+    assert_eq!(energies, vec![1.0, 5.0, 10.0, 50.0, 100.0]);
+    assert_eq!(analyzer.filter_energy_range(20.0, 60.0), 1);
+    assert_eq!(analyzer.measurements()[0].energy, 50.0);
+}
+
+#[test]
+#[should_panic(expected = "energy bounds must be non-negative")]
+fn test_filter_energy_range_rejects_negative_bound() {
+    graded_anomaly_analyzer().filter_energy_range(-1.0, 10.0);
+}
+
+#[test]
+#[should_panic(expected = "minimum energy must be below the maximum")]
+fn test_filter_energy_range_rejects_inverted_band() {
+    graded_anomaly_analyzer().filter_energy_range(10.0, 1.0);
+}
+
+#[test]
+fn test_reduced_chi_squared_divides_by_degrees_of_freedom() {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for (i, &offset) in [1.0, -2.0, 0.5, 3.0, -1.5].iter().enumerate() {
+        analyzer.add_measurement_with_distance(1.0 + i as f64, travel_time + offset, 1.0, distance);
+    }
+    let result = analyzer.test_light_speed_constancy();
+    assert_eq!(result.degrees_of_freedom, 4);
+    assert!(result.chi_squared > 0.0);
+    assert_eq!(result.reduced_chi_squared, result.chi_squared / 4.0);
+
+    let mut single = GammaRayAnalyzer::new();
+    single.add_measurement_with_distance(1.0, travel_time + 1.0, 1.0, distance);
+    let result = single.test_light_speed_constancy();
+    assert_eq!(result.degrees_of_freedom, 0);
+    assert!(result.reduced_chi_squared.is_nan());
+}
+
+#[test]
+fn test_single_measurement_is_insufficient_for_inference() {
+    let distance = 1e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    // Ten sigma late, which would be an anomaly among other photons
+    analyzer.add_measurement_with_distance(1.0, travel_time + 10.0, 1.0, distance);
+
+    let result = analyzer.test_light_speed_constancy();
+    assert!(result.insufficient_data);
+    assert!(result.is_valid);
+    assert_eq!(result.p_value, 1.0);
+    assert_eq!(result.confidence_level, 0.0);
+    assert_eq!(result.degrees_of_freedom, 0);
+    assert!((result.chi_squared - 100.0).abs() < 1e-6);
+    assert!((result.deviation_estimate.unwrap() - 10.0).abs() < 1e-6);
+    assert_eq!(result.anomalies_detected, 0);
+    assert!(analyzer.detect_anomalies().is_empty());
+
+    analyzer.add_measurement_with_distance(2.0, travel_time, 1.0, distance);
+    let result = analyzer.test_light_speed_constancy();
+    assert!(!result.insufficient_data);
+    assert_eq!(result.degrees_of_freedom, 1);
+    assert_eq!(analyzer.detect_anomalies().len(), 1);
+}
+
+#[test]
+fn test_streaming_without_geometry_matches_in_memory() {
+    let data: Vec<TimingData> = (0..50)
+        .map(|i| {
+            let scatter = ((i * 37) % 11) as f64 * 1e-3 - 5e-3;
+            let outlier = if i == 17 { 0.5 } else { 0.0 };
+            TimingData::new(1.0 + i as f64, 1000.0 + scatter + outlier, 0.01)
+        })
+        .collect();
+    let mut in_memory = GammaRayAnalyzer::new();
+    in_memory.set_reference_time(1000.0);
+    in_memory.add_measurements(&data);
+    let expected = in_memory.test_light_speed_constancy();
+    assert_eq!(expected.anomalies_detected, 1);
+
+    let mut streaming = GammaRayAnalyzer::new();
+    streaming.set_reference_time(1000.0);
+    for measurement in &data {
+        streaming.accumulate(measurement);
+    }
+    let result = streaming.finalize_streaming();
+    assert_eq!(result.chi_squared, expected.chi_squared);
+    assert_eq!(result.p_value, expected.p_value);
+    assert_eq!(result.deviation_estimate, expected.deviation_estimate);
+    assert_eq!(result.deviation_error, expected.deviation_error);
+    assert_eq!(result.anomalies_detected, expected.anomalies_detected);
+}
+
+#[test]
+fn test_streaming_ignores_huber_loss() {
+    let data: Vec<TimingData> = (0..20)
+        .map(|i| {
+            let outlier = if i == 7 { 0.5 } else { 0.0 };
+            TimingData::new(1.0 + i as f64, 100.0 + (i % 3) as f64 * 1e-3 + outlier, 0.01)
+        })
+        .collect();
+    let mut in_memory = GammaRayAnalyzer::new();
+    in_memory.set_reference_time(100.0);
+    in_memory.add_measurements(&data);
+    let least_squares = in_memory.test_light_speed_constancy().chi_squared;
+    in_memory.set_loss(LossKind::Huber { delta: 1.345 });
+    assert!(in_memory.test_light_speed_constancy().chi_squared < least_squares);
+
+    let mut streaming = GammaRayAnalyzer::new();
+    streaming.set_reference_time(100.0);
+    streaming.set_loss(LossKind::Huber { delta: 1.345 });
+    for measurement in &data {
+        streaming.accumulate(measurement);
+    }
+    assert_eq!(streaming.finalize_streaming().chi_squared, least_squares);
+}
+
+#[test]
+#[should_panic(expected = "set_reference_time")]
+fn test_streaming_without_geometry_needs_reference_time() {
+    GammaRayAnalyzer::new().accumulate(&TimingData::new(1.0, 10.0, 1.0));
+}
+
+#[test]
+fn test_streaming_single_measurement_is_insufficient() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.set_reference_time(0.0);
+    analyzer.accumulate(&TimingData::new(1.0, 10.0, 1.0));
+    let result = analyzer.finalize_streaming();
+    assert!(result.insufficient_data);
+    assert_eq!(result.anomalies_detected, 0);
+    assert!(GammaRayAnalyzer::new().finalize_streaming().insufficient_data);
+}
+
+#[test]
+fn test_try_constancy_rejects_empty_data() {
+    let analyzer = GammaRayAnalyzer::new();
+    assert_eq!(analyzer.try_test_light_speed_constancy().unwrap_err(), AnalysisError::NoData);
+    // The infallible method keeps its trivially valid default
+    let result = analyzer.test_light_speed_constancy();
+    assert!(result.is_valid);
+    assert_eq!(result.p_value, 1.0);
+}
+
+#[test]
+fn test_try_constancy_rejects_zero_error() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(1.0, 0.0, 0.01);
+    analyzer.add_measurement(2.0, 0.0, 0.0);
+    analyzer.add_measurement(3.0, 0.0, f64::NAN);
+    assert_eq!(
+        analyzer.try_test_light_speed_constancy().unwrap_err(),
+        AnalysisError::ZeroError { index: 1 }
+    );
+}
+
+#[test]
+fn test_try_constancy_matches_infallible_on_clean_data() {
+    let analyzer = graded_anomaly_analyzer();
+    let checked = analyzer.try_test_light_speed_constancy().unwrap();
+    let unchecked = analyzer.test_light_speed_constancy();
+    assert_eq!(checked.chi_squared, unchecked.chi_squared);
+    assert_eq!(checked.p_value, unchecked.p_value);
+}
+
+fn bound_analyzer(error: f64) -> GammaRayAnalyzer {
+    let distance = 1.0e16;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    for (i, scatter) in [0.002, -0.001, 0.0, 0.001, -0.002].iter().enumerate() {
+        let energy = 10.0 * (i + 1) as f64;
+        analyzer.add_measurement_with_distance(energy, travel_time + scatter, error, distance);
+    }
+    analyzer
+}
+
+#[test]
+fn test_quantum_gravity_energy_bound_tightens_with_precision() {
+    let coarse = bound_analyzer(0.1).quantum_gravity_energy_bound(0.95).unwrap();
+    let fine = bound_analyzer(0.01).quantum_gravity_energy_bound(0.95).unwrap();
+    assert!(fine > coarse);
+    // A higher confidence gives a more conservative (lower) bound
+    let strict = bound_analyzer(0.01).quantum_gravity_energy_bound(0.99).unwrap();
+    assert!(strict < fine);
+}
+
+#[test]
+fn test_quantum_gravity_energy_bound_needs_distance() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement(10.0, 0.0, 0.01);
+    analyzer.add_measurement(20.0, 0.001, 0.01);
+    assert_eq!(analyzer.quantum_gravity_energy_bound(0.95), None);
+}
+
+#[test]
+fn test_quantum_gravity_energy_bound_needs_a_single_source() {
+    let mut analyzer = bound_analyzer(0.01);
+    assert!(analyzer.quantum_gravity_energy_bound(0.95).is_some());
+    // A nearer source makes the travel time, and so the bound, ambiguous
+    let distance = 5.0e15;
+    analyzer.add_measurement_with_distance(60.0, distance / SPEED_OF_LIGHT, 0.01, distance);
+    assert_eq!(analyzer.quantum_gravity_energy_bound(0.95), None);
+
+    let mut partial = bound_analyzer(0.01);
+    partial.add_measurement(60.0, 1.0e16 / SPEED_OF_LIGHT, 0.01);
+    assert_eq!(partial.quantum_gravity_energy_bound(0.95), None);
+}
+
+#[test]
+fn test_sources_give_each_photon_its_geometric_delay() {
+    let cosmology = Cosmology::planck_2018();
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.set_cosmology(cosmology.clone());
+    analyzer.add_source("GRB090510", SourceGeometry::Distance(1.0e25));
+    analyzer.add_source("GRB080916C", SourceGeometry::Redshift(4.35));
+    analyzer.add_measurements(&[
+        TimingData::new(10.0, 0.0, 0.01).with_source("GRB090510"),
+        TimingData::new(20.0, 0.0, 0.01).with_source("GRB080916C"),
+        // Unknown source falls back to the photon's own distance
+        TimingData::new(30.0, 0.0, 0.01).with_source("unregistered").with_distance(2.0e25),
+        TimingData::new(40.0, 5.0, 0.01),
+    ]);
+
+    let expected = analyzer.calculate_expected_arrivals();
+    assert_eq!(expected[0].1, 1.0e25 / SPEED_OF_LIGHT);
+    assert_eq!(expected[1].1, cosmology.comoving_distance(4.35) / SPEED_OF_LIGHT);
+    assert_eq!(expected[2].1, 2.0e25 / SPEED_OF_LIGHT);
+    assert_eq!(expected[3].1, 5.0);
+}
+
+#[test]
+fn test_add_source_invalidates_expected_arrivals() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurements(&[TimingData::new(10.0, 0.0, 0.01).with_source("A")]);
+    assert_eq!(analyzer.calculate_expected_arrivals()[0].1, 0.0);
+    analyzer.add_source("A", SourceGeometry::Distance(3.0e8));
+    assert!((analyzer.calculate_expected_arrivals()[0].1 - 3.0e8 / SPEED_OF_LIGHT).abs() < 1e-12);
+    assert_eq!(analyzer.sources().len(), 1);
+}
+
+#[test]
+fn test_progress_callback_reports_increasing_fractions() {
+    use std::sync::{Arc, Mutex};
+
+    let mut analyzer = GammaRayAnalyzer::new();
+    for i in 0..250 {
+        analyzer.add_measurement_with_distance(1.0 + i as f64, 1.0, 0.01, 3.0e8);
+    }
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    analyzer.set_progress_callback(Box::new(move |fraction| sink.lock().unwrap().push(fraction)));
+
+    let with_progress = analyzer.test_light_speed_constancy();
+    let fractions = seen.lock().unwrap().clone();
+    assert!(fractions.len() >= 2);
+    assert!(fractions.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(fractions.iter().all(|&f| (0.0..=1.0).contains(&f)));
+    assert_eq!(*fractions.last().unwrap(), 1.0);
+
+    analyzer.clear_progress_callback();
+    let without_progress = analyzer.test_light_speed_constancy();
+    assert_eq!(with_progress.chi_squared, without_progress.chi_squared);
+    assert_eq!(seen.lock().unwrap().len(), fractions.len());
+}
@@ -0,0 +1,424 @@
+use lightspeedvalidator::{
+    AnalyzeArgs, CliError, CliExecutor, CompareArgs, CsvExporter, DataError, DataExporter,
+    InputFormat, JsonExporter, ReportArgs, TimingData, TimingDataError,
+};
+use std::path::{Path, PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("lsv_cli_{}_{}", std::process::id(), name))
+}
+
+fn sample_data() -> Vec<TimingData> {
+    vec![
+        TimingData::new(1.0, 0.0, 0.01),
+        TimingData::new(10.0, 0.02, 0.01),
+        TimingData::new(100.0, -0.01, 0.01),
+    ]
+}
+
+fn write_ascii(path: &Path, data: &[TimingData]) {
+    let mut contents = String::from("# energy arrival_time error\n");
+    for m in data {
+        contents.push_str(&format!("{} {} {}\n", m.energy, m.arrival_time, m.error));
+    }
+    std::fs::write(path, contents).unwrap();
+}
+
+fn analyze_args(input: &Path, format: InputFormat, output: &Path) -> AnalyzeArgs {
+    AnalyzeArgs {
+        input: vec![input.to_path_buf()],
+        format,
+        output: Some(output.to_path_buf()),
+        include_input: true,
+        include_config: false,
+        anomalies_output: None,
+        sensitivity: 1e-12,
+        confidence: 0.95,
+        quantum_gravity: false,
+        histogram: None,
+        validate_only: false,
+        json_output: false,
+        quiet: false,
+        verbose: false,
+    }
+}
+
+fn analyzed_count(output: &Path) -> usize {
+    let contents = std::fs::read_to_string(output).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    document["measurements"].as_array().unwrap().len()
+}
+
+#[test]
+fn test_explicit_formats_ignore_extension() {
+    let data = sample_data();
+    let csv = temp_path("explicit_csv.data");
+    let json = temp_path("explicit_json.data");
+    let ascii = temp_path("explicit_ascii.data");
+    CsvExporter.save_to_file(&data, &csv).unwrap();
+    JsonExporter.save_to_file(&data, &json).unwrap();
+    write_ascii(&ascii, &data);
+
+    for (input, format) in [
+        (&csv, InputFormat::Csv),
+        (&json, InputFormat::Json),
+        (&ascii, InputFormat::Ascii),
+    ] {
+        let output = temp_path("explicit_out.json");
+        CliExecutor::execute(analyze_args(input, format, &output)).unwrap();
+        assert_eq!(analyzed_count(&output), data.len(), "format {:?}", format);
+        std::fs::remove_file(&output).unwrap();
+    }
+
+    for path in [csv, json, ascii] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_auto_format_dispatches_on_extension() {
+    let data = sample_data();
+    let csv = temp_path("auto.csv");
+    let json = temp_path("auto.JSON");
+    let ascii = temp_path("auto.dat");
+    CsvExporter.save_to_file(&data, &csv).unwrap();
+    JsonExporter.save_to_file(&data, &json).unwrap();
+    write_ascii(&ascii, &data);
+
+    for input in [&csv, &json, &ascii] {
+        let output = temp_path("auto_out.json");
+        CliExecutor::execute(analyze_args(input, InputFormat::Auto, &output)).unwrap();
+        assert_eq!(analyzed_count(&output), data.len(), "input {}", input.display());
+        std::fs::remove_file(&output).unwrap();
+    }
+
+    for path in [csv, json, ascii] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_confidence_flag_sets_validity_threshold() {
+    // Two photons 1.6σ late give p ≈ 0.024
+    let distance = 1e12;
+    let travel_time = distance / lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+    let data: Vec<TimingData> = [1.0, 2.0]
+        .iter()
+        .map(|&energy| TimingData::new(energy, travel_time + 1.6, 1.0).with_distance(distance))
+        .collect();
+    let input = temp_path("confidence.json");
+    JsonExporter.save_to_file(&data, &input).unwrap();
+
+    let is_valid = |confidence: f64| {
+        let output = temp_path("confidence_out.json");
+        let mut args = analyze_args(&input, InputFormat::Auto, &output);
+        args.confidence = confidence;
+        CliExecutor::execute(args).unwrap();
+        let contents = std::fs::read_to_string(&output).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        document["result"]["is_valid"].as_bool().unwrap()
+    };
+    assert!(!is_valid(0.90));
+    assert!(is_valid(0.999));
+
+    let output = temp_path("confidence_config.json");
+    let mut args = analyze_args(&input, InputFormat::Auto, &output);
+    args.confidence = 0.99;
+    args.include_config = true;
+    CliExecutor::execute(args).unwrap();
+    let document: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    std::fs::remove_file(&output).unwrap();
+    assert!((document["config"]["significance_level"].as_f64().unwrap() - 0.01).abs() < 1e-12);
+
+    let mut args = analyze_args(&input, InputFormat::Auto, &temp_path("confidence_bad.json"));
+    args.confidence = 1.5;
+    let err = CliExecutor::execute(args).unwrap_err();
+    assert!(matches!(err, CliError::InvalidArgs(_)));
+    assert!(err.to_string().contains("confidence must be in (0, 1)"));
+
+    std::fs::remove_file(input).unwrap();
+}
+
+#[test]
+fn test_histogram_flag_rejects_zero_bins() {
+    let input = temp_path("histogram_in.csv");
+    CsvExporter.save_to_file(&sample_data(), &input).unwrap();
+    let mut args = analyze_args(&input, InputFormat::Auto, &temp_path("histogram_out.json"));
+    args.output = None;
+
+    args.histogram = Some(5);
+    CliExecutor::execute(args.clone()).unwrap();
+    args.histogram = Some(0);
+    let err = CliExecutor::execute(args).unwrap_err();
+    assert!(matches!(err, CliError::InvalidArgs(_)), "got {:?}", err);
+
+    std::fs::remove_file(input).unwrap();
+}
+
+#[test]
+fn test_anomalies_output_writes_detected_anomalies() {
+    let distance = 1e12;
+    let travel_time = distance / lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+    let data: Vec<TimingData> = [(1.0, 0.0), (2.0, -5.0), (3.0, 4.0)]
+        .iter()
+        .map(|&(energy, offset)| TimingData::new(energy, travel_time + offset, 1.0).with_distance(distance))
+        .collect();
+    let input = temp_path("anomalies_in.json");
+    JsonExporter.save_to_file(&data, &input).unwrap();
+
+    let anomalies = temp_path("anomalies_out.csv");
+    let mut args = analyze_args(&input, InputFormat::Auto, &temp_path("anomalies_result.json"));
+    args.output = None;
+    args.anomalies_output = Some(anomalies.clone());
+    CliExecutor::execute(args).unwrap();
+
+    let contents = std::fs::read_to_string(&anomalies).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines[0], "energy,measured_time,expected_time,deviation,significance");
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with("2,"));
+
+    std::fs::remove_file(input).unwrap();
+    std::fs::remove_file(anomalies).unwrap();
+}
+
+#[test]
+fn test_auto_format_rejects_unknown_extension() {
+    let input = temp_path("auto.xyz");
+    let output = temp_path("unknown_out.json");
+    CsvExporter.save_to_file(&sample_data(), &input).unwrap();
+
+    let err = CliExecutor::execute(analyze_args(&input, InputFormat::Auto, &output)).unwrap_err();
+    assert!(err.to_string().contains("cannot infer the input format"));
+    assert!(!output.exists());
+
+    std::fs::remove_file(input).unwrap();
+}
+
+#[test]
+fn test_multiple_inputs_are_pooled_and_tagged_by_file() {
+    let first = temp_path("pooled_first.csv");
+    let second = temp_path("pooled_second.csv");
+    let output = temp_path("pooled_out.json");
+    CsvExporter.save_to_file(&sample_data(), &first).unwrap();
+    CsvExporter.save_to_file(&sample_data()[..2], &second).unwrap();
+
+    let mut args = analyze_args(&first, InputFormat::Auto, &output);
+    args.input.push(second.clone());
+    CliExecutor::execute(args).unwrap();
+    assert_eq!(analyzed_count(&output), 5);
+
+    let contents = std::fs::read_to_string(&output).unwrap();
+    let document: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let sources: Vec<&str> = document["measurements"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["source_id"].as_str().unwrap())
+        .collect();
+    let first_name = first.display().to_string();
+    let second_name = second.display().to_string();
+    assert_eq!(sources[..3], [first_name.as_str(); 3]);
+    assert_eq!(sources[3..], [second_name.as_str(); 2]);
+
+    for path in [first, second, output] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_multiple_inputs_must_share_a_format() {
+    let csv = temp_path("mixed.csv");
+    let json = temp_path("mixed.json");
+    let output = temp_path("mixed_out.json");
+    CsvExporter.save_to_file(&sample_data(), &csv).unwrap();
+    JsonExporter.save_to_file(&sample_data(), &json).unwrap();
+
+    let mut args = analyze_args(&csv, InputFormat::Auto, &output);
+    args.input.push(json.clone());
+    let err = CliExecutor::execute(args).unwrap_err();
+    assert!(matches!(err, CliError::InvalidArgs(_)), "got {:?}", err);
+    assert!(err.to_string().contains("incompatible formats"), "{}", err);
+    assert!(!output.exists());
+
+    for path in [csv, json] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+/// Runs the `analyze` subcommand of the built binary, returning its output
+fn run_analyze(input: &Path, flags: &[&str]) -> std::process::Output {
+    std::process::Command::new(env!("CARGO_BIN_EXE_lightspeedvalidator"))
+        .arg("analyze")
+        .arg("--input")
+        .arg(input)
+        .args(flags)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_json_output_prints_single_result_object() {
+    let input = temp_path("json_output.csv");
+    CsvExporter.save_to_file(&sample_data(), &input).unwrap();
+
+    let output = run_analyze(&input, &["--json-output"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "{}", stdout);
+    let result: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    for key in ["is_valid", "chi_squared", "degrees_of_freedom", "p_value", "anomalies_detected"] {
+        assert!(result.get(key).is_some(), "missing {} in {}", key, stdout);
+    }
+    assert_eq!(result["degrees_of_freedom"], 2);
+    std::fs::remove_file(&input).unwrap();
+}
+
+#[test]
+fn test_quiet_suppresses_output_and_conflicts_with_verbose() {
+    let input = temp_path("quiet.csv");
+    CsvExporter.save_to_file(&sample_data(), &input).unwrap();
+
+    let output = run_analyze(&input, &["--quiet", "--histogram"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty() && output.stderr.is_empty());
+
+    let output = run_analyze(&input, &["--quiet", "--verbose"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run_analyze(&input, &["--json-output", "--verbose"]);
+    assert_eq!(output.status.code(), Some(2));
+    std::fs::remove_file(&input).unwrap();
+}
+
+#[test]
+fn test_missing_input_is_a_load_error() {
+    let input = temp_path("missing_input.csv");
+    let output = temp_path("missing_out.json");
+
+    let err = CliExecutor::execute(analyze_args(&input, InputFormat::Auto, &output)).unwrap_err();
+    assert!(matches!(err, CliError::Load(DataError::Io(_))), "got {:?}", err);
+    assert_eq!(err.exit_code(), 3);
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_validate_only_reports_rejected_rows_and_skips_analysis() {
+    let mut data = sample_data();
+    data.insert(1, TimingData::new(-5.0, 0.01, 0.01));
+    data.push(TimingData::new(50.0, 0.03, 0.0));
+    let input = temp_path("validate_only.csv");
+    let output = temp_path("validate_only_out.json");
+    CsvExporter.save_to_file(&data, &input).unwrap();
+
+    let inputs = [input.clone()];
+    let summary = CliExecutor::validate_inputs(&inputs, InputFormat::Auto).unwrap();
+    assert_eq!(summary.total, 5);
+    assert_eq!(summary.accepted(), 3);
+    let rows: Vec<usize> = summary.rejected.iter().map(|(row, _)| *row).collect();
+    assert_eq!(rows, vec![2, 5]);
+    assert_eq!(summary.rejected[0].1, TimingDataError::NonPositiveEnergy(-5.0));
+    assert_eq!(summary.rejected[1].1, TimingDataError::NonPositiveError(0.0));
+    assert!(summary.to_string().contains("Rejected: 2"));
+
+    let mut args = analyze_args(&input, InputFormat::Auto, &output);
+    args.validate_only = true;
+    CliExecutor::execute(args).unwrap();
+    assert!(!output.exists());
+    std::fs::remove_file(&input).unwrap();
+}
+
+#[test]
+fn test_report_contains_key_fields_and_anomaly_table() {
+    let distance = 1.0e16;
+    let travel_time = distance / lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+    let mut data: Vec<TimingData> = sample_data()
+        .into_iter()
+        .map(|m| TimingData::new(m.energy, travel_time + m.arrival_time, m.error))
+        .collect();
+    // A photon far off the expected arrival so the anomaly table is populated
+    data.push(TimingData::new(50.0, travel_time + 5.0, 0.01));
+    let input = temp_path("report_in.csv");
+    let output = temp_path("report_out.md");
+    CsvExporter.save_to_file(&data, &input).unwrap();
+
+    let args = ReportArgs {
+        input: input.clone(),
+        format: InputFormat::Auto,
+        output: Some(output.clone()),
+        sensitivity: 1e-12,
+        distance: Some(distance),
+        redshift: None,
+    };
+    CliExecutor::report(args).unwrap();
+    let report = std::fs::read_to_string(&output).unwrap();
+
+    assert!(report.contains("| Chi-squared |"));
+    assert!(report.contains("| P-value |"));
+    assert!(report.contains("| Slope (s/GeV) |"));
+    assert!(report.contains("| E_QG lower bound (GeV, 95%) |"));
+    assert!(!report.contains("| E_QG lower bound (GeV, 95%) | n/a |"));
+    assert!(report.contains("| Energy (GeV) | Measured (s) | Expected (s) | Deviation (s) | Significance (σ) |"));
+    assert!(report.contains("| 50.000000 |"));
+    assert!(!report.contains("No anomalies detected."));
+
+    std::fs::remove_file(input).unwrap();
+    std::fs::remove_file(output).unwrap();
+}
+
+fn write_offset_dataset(path: &Path, travel_time: f64, offset: f64) {
+    // Alternating ±0.01 s scatter around a common offset from the light-travel time
+    let data: Vec<TimingData> = (0..20)
+        .map(|i| {
+            let scatter = if i % 2 == 0 { 0.01 } else { -0.01 };
+            TimingData::new(1.0 + i as f64, travel_time + offset + scatter, 0.01)
+        })
+        .collect();
+    CsvExporter.save_to_file(&data, path).unwrap();
+}
+
+fn compare_consistency(first_offset: f64, second_offset: f64, name: &str) -> (bool, f64) {
+    let distance = 1.0e16;
+    let travel_time = distance / lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+    let first = temp_path(&format!("{}_a.csv", name));
+    let second = temp_path(&format!("{}_b.csv", name));
+    let output = temp_path(&format!("{}_out.json", name));
+    write_offset_dataset(&first, travel_time, first_offset);
+    write_offset_dataset(&second, travel_time, second_offset);
+
+    let args = CompareArgs {
+        first: first.clone(),
+        second: second.clone(),
+        format: InputFormat::Auto,
+        output: Some(output.clone()),
+        alpha: 0.05,
+        distance: Some(distance),
+        redshift: None,
+    };
+    CliExecutor::compare(args).unwrap();
+    let document: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    assert!(document["first"]["chi_squared"].is_number());
+    assert!(document["second"]["deviation_estimate"].is_number());
+
+    for path in [first, second, output] {
+        std::fs::remove_file(path).unwrap();
+    }
+    let comparison = &document["comparison"];
+    (comparison["is_consistent"].as_bool().unwrap(), comparison["p_value"].as_f64().unwrap())
+}
+
+#[test]
+fn test_compare_identical_datasets_are_consistent() {
+    let (consistent, p_value) = compare_consistency(0.05, 0.05, "compare_same");
+    assert!(consistent);
+    assert!(p_value > 0.99);
+}
+
+#[test]
+fn test_compare_opposite_deviations_are_inconsistent() {
+    let (consistent, p_value) = compare_consistency(0.05, -0.05, "compare_opposite");
+    assert!(!consistent);
+    assert!(p_value < 1e-6);
+}
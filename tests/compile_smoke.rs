@@ -0,0 +1,88 @@
+// Imports every public type so a broken module layout fails the build here.
+
+#[allow(unused_imports)]
+use lightspeedvalidator::{
+    AnalysisConfig,
+    AnalysisError,
+    AnalysisReport,
+    AnalyzeArgs,
+    AnomalyDetectionResult,
+    AnomalyExporter,
+    AnomalyKind,
+    AsciiImporter,
+    BandSummary,
+    BayesianResult,
+    ChiSquaredResult,
+    CiMethod,
+    CombinedLimit,
+    Cli,
+    CliError,
+    CliExecutor,
+    ColumnMap,
+    Commands,
+    CompareArgs,
+    ConfidenceInterval,
+    Cosmology,
+    CrossCorrelationResult,
+    CsvExporter,
+    CsvImporter,
+    DataError,
+    DataExporter,
+    DataFormat,
+    DataImporter,
+    DisCanResult,
+    DispersionOrder,
+    DistanceTable,
+    EnergyUnit,
+    ErrorPolicy,
+    FTestResult,
+    GammaRayAnalyzer,
+    HypothesisTestResult,
+    IcResult,
+    InputFormat,
+    JackknifeResult,
+    JsonExporter,
+    JsonImporter,
+    KsResult,
+    LightSpeedTestResult,
+    LivFitResult,
+    LoadReport,
+    LossKind,
+    LrtResult,
+    MergeError,
+    MixtureResult,
+    ModelComparison,
+    MwuResult,
+    NdjsonImporter,
+    PowerLawSpectrum,
+    ProgressCallback,
+    QuantumGravityModel,
+    RegressionResult,
+    ReportArgs,
+    ResultExporter,
+    ShapiroResult,
+    SimulateArgs,
+    SimulationConfig,
+    SourceGeometry,
+    SpectralLag,
+    SpectralLagBin,
+    SpeedOfLightHypothesisTest,
+    TimeReference,
+    TimingData,
+    TimingDataError,
+    ValidationSummary,
+    ZTestResult,
+};
+
+#[allow(unused_imports)]
+use lightspeedvalidator::{analyzer, cli, cosmology, data, report, simulation, statistics, stats_math};
+
+#[test]
+fn test_public_types_are_reachable() {
+    let analyzer = GammaRayAnalyzer::new();
+    let result: LightSpeedTestResult = analyzer.test_light_speed_constancy();
+    assert!(result.p_value <= 1.0);
+
+    let chi: ChiSquaredResult = SpeedOfLightHypothesisTest::chi_squared_test(&[]);
+    assert_eq!(chi.degrees_of_freedom, 0);
+}
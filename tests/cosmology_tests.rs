@@ -0,0 +1,148 @@
+use lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+use lightspeedvalidator::cosmology::MEGAPARSEC_M;
+use lightspeedvalidator::{Cosmology, DispersionOrder, GammaRayAnalyzer, QuantumGravityModel};
+
+fn assert_within_percent(actual: f64, expected: f64, percent: f64) {
+    let relative = (actual - expected).abs() / expected.abs();
+    assert!(
+        relative <= percent / 100.0,
+        "expected {} within {}%, got {}",
+        expected,
+        percent,
+        actual
+    );
+}
+
+#[test]
+fn test_comoving_distance_planck_2018() {
+    let cosmology = Cosmology::planck_2018();
+    // Flat ΛCDM reference values for H0 = 67.4, Ωm = 0.315
+    assert_within_percent(cosmology.comoving_distance(1.0) / MEGAPARSEC_M, 3401.3, 1.0);
+    assert_within_percent(cosmology.comoving_distance(0.5) / MEGAPARSEC_M, 1951.4, 1.0);
+}
+
+#[test]
+fn test_comoving_distance_low_redshift_hubble_law() {
+    let cosmology = Cosmology::planck_2018();
+    let z = 1e-4;
+    assert_within_percent(cosmology.comoving_distance(z), z * cosmology.hubble_distance(), 0.01);
+}
+
+#[test]
+fn test_comoving_distance_zero_and_monotonic() {
+    let cosmology = Cosmology::default();
+    assert_eq!(cosmology.comoving_distance(0.0), 0.0);
+    let mut previous = 0.0;
+    for i in 1..=20 {
+        let distance = cosmology.comoving_distance(i as f64 * 0.25);
+        assert!(distance > previous);
+        previous = distance;
+    }
+}
+
+#[test]
+fn test_expected_arrivals_use_redshift_with_cosmology() {
+    let cosmology = Cosmology::planck_2018();
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.set_cosmology(cosmology.clone());
+    analyzer.add_measurement_with_redshift(100.0, 0.0, 0.001, 1.0);
+
+    let expected = analyzer.calculate_expected_arrivals();
+    let travel_time = cosmology.comoving_distance(1.0) / SPEED_OF_LIGHT;
+    assert_within_percent(expected[0].1, travel_time, 1e-9);
+}
+
+#[test]
+fn test_redshift_ignored_without_cosmology() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement_with_redshift(100.0, 42.0, 0.001, 1.0);
+    assert_eq!(analyzer.calculate_expected_arrivals()[0].1, 42.0);
+}
+
+#[test]
+fn test_liv_time_delay_matches_dispersion_integrals() {
+    let cosmology = Cosmology::planck_2018();
+    let hubble_time = cosmology.hubble_distance() / SPEED_OF_LIGHT;
+    // ∫₀^z (1+z')^n / E(z') dz' from a fine Simpson's rule for Ωm = 0.315, ΩΛ = 0.685
+    let cases = [
+        (0.5, DispersionOrder::Linear, 0.543_273_387_433_788),
+        (1.0, DispersionOrder::Linear, 1.109_589_608_377_627),
+        (3.0, DispersionOrder::Linear, 3.095_091_361_460_949),
+        (0.5, DispersionOrder::Quadratic, 0.681_834_459_720_073),
+        (1.0, DispersionOrder::Quadratic, 1.672_489_690_998_569),
+        (3.0, DispersionOrder::Quadratic, 7.546_312_140_327_284),
+    ];
+    let ratio: f64 = 1e-3;
+    for (z, order, integral) in cases {
+        let n = order.exponent();
+        let expected = (1.0 + n as f64) / 2.0 * ratio.powi(n) * hubble_time * integral;
+        let delay = cosmology.liv_time_delay(z, 1.0, 1.0 / ratio, order);
+        assert_within_percent(delay, expected, 1e-6);
+    }
+    assert_eq!(cosmology.liv_time_delay(0.0, 1.0, 1e3, DispersionOrder::Linear), 0.0);
+}
+
+#[test]
+fn test_distance_table_matches_direct_integration() {
+    let cosmology = Cosmology::planck_2018();
+    let table = cosmology.build_distance_table(5.0, 500);
+    assert_eq!(table.z_max(), 5.0);
+    assert_eq!(table.interpolate(0.0), 0.0);
+    for i in 1..=997 {
+        let z = i as f64 * 0.005_013;
+        assert_within_percent(table.interpolate(z), cosmology.comoving_distance(z), 1e-6);
+    }
+    // Beyond the grid the table integrates directly
+    assert_eq!(table.interpolate(6.0), cosmology.comoving_distance(6.0));
+}
+
+#[test]
+#[should_panic(expected = "at least one step")]
+fn test_distance_table_rejects_zero_steps() {
+    Cosmology::planck_2018().build_distance_table(1.0, 0);
+}
+
+#[test]
+fn test_expected_arrivals_match_direct_integration_for_any_photon_count() {
+    let cosmology = Cosmology::planck_2018();
+    for count in [2, 1000] {
+        let mut analyzer = GammaRayAnalyzer::new();
+        analyzer.set_cosmology(cosmology.clone());
+        for i in 0..count {
+            let z = if i % 2 == 0 { 0.5 } else { 2.0 };
+            analyzer.add_measurement_with_redshift(1.0 + i as f64, 0.0, 1.0, z);
+        }
+
+        let expected = analyzer.calculate_expected_arrivals();
+        for (i, (_, time)) in expected.iter().enumerate() {
+            let z = if i % 2 == 0 { 0.5 } else { 2.0 };
+            assert_eq!(*time, cosmology.comoving_distance(z) / SPEED_OF_LIGHT);
+        }
+    }
+}
+
+#[test]
+fn test_expected_arrivals_use_jacob_piran_delay_for_redshift_sources() {
+    let cosmology = Cosmology::planck_2018();
+    let z = 1.0;
+    let energy_scale = 1e14;
+    let travel_time = cosmology.comoving_distance(z) / SPEED_OF_LIGHT;
+
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.set_cosmology(cosmology.clone());
+    analyzer.enable_quantum_gravity_simulation(QuantumGravityModel {
+        planck_length: 1.616e-35,
+        quantum_gravity_effect: 0.0,
+        energy_scale,
+        dispersion_order: DispersionOrder::Linear,
+    });
+    for &energy in &[100.0, 500.0, 1000.0] {
+        let delay = cosmology.liv_time_delay(z, energy, energy_scale, DispersionOrder::Linear);
+        analyzer.add_measurement_with_redshift(energy, travel_time + delay, 1e3, z);
+    }
+
+    // Photons delayed by the integrated formula match the model's expectation
+    let result = analyzer.test_light_speed_constancy();
+    assert!(result.chi_squared < 1e-3, "{}", result.chi_squared);
+    assert_eq!(analyzer.detect_anomalies().len(), 0);
+}
@@ -0,0 +1,707 @@
+use lightspeedvalidator::{
+    AnomalyExporter, AsciiImporter, ColumnMap, CsvExporter, CsvImporter, DataError, DataExporter,
+    DataImporter, EnergyUnit, ErrorPolicy, GammaRayAnalyzer, JsonExporter, JsonImporter,
+    ResultExporter, TimingData,
+};
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("lsv_{}_{}", std::process::id(), name))
+}
+
+fn sample_data() -> Vec<TimingData> {
+    vec![
+        TimingData::new(100.0, 1234567890.123, 0.001),
+        TimingData::new(200.0, 1234567891.456, 0.002),
+    ]
+}
+
+fn assert_same(a: &[TimingData], b: &[TimingData]) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b) {
+        assert_eq!(x.energy, y.energy);
+        assert_eq!(x.arrival_time, y.arrival_time);
+        assert_eq!(x.error, y.error);
+        assert_eq!(x.distance_m, y.distance_m);
+        assert_eq!(x.redshift, y.redshift);
+    }
+}
+
+#[test]
+fn test_timing_data_json_round_trip() {
+    let data = sample_data();
+    let json = serde_json::to_string(&data).unwrap();
+    assert!(json.contains("\"energy\""));
+    assert!(json.contains("\"arrival_time\""));
+    assert!(json.contains("\"error\""));
+
+    let parsed: Vec<TimingData> = serde_json::from_str(&json).unwrap();
+    assert_same(&data, &parsed);
+}
+
+#[test]
+fn test_timing_data_geometry_round_trip() {
+    let data = vec![TimingData::new(100.0, 12.5, 0.001).with_distance(1.0e22).with_redshift(0.9)];
+    let json = serde_json::to_string(&data).unwrap();
+    assert!(json.contains("\"distance_m\""));
+    assert!(json.contains("\"redshift\""));
+
+    let parsed: Vec<TimingData> = serde_json::from_str(&json).unwrap();
+    assert_same(&data, &parsed);
+}
+
+#[test]
+fn test_timing_data_without_geometry_omits_fields() {
+    let json = serde_json::to_string(&sample_data()).unwrap();
+    assert!(!json.contains("distance_m"));
+    assert!(!json.contains("redshift"));
+}
+
+#[test]
+fn test_json_exporter_output_deserializes() {
+    let data = sample_data();
+    let path = temp_path("export.json");
+    JsonExporter.save_to_file(&data, &path).unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    let parsed: Vec<TimingData> = serde_json::from_str(&contents).unwrap();
+    assert_same(&data, &parsed);
+}
+
+#[test]
+fn test_json_importer_valid_file() {
+    let path = temp_path("import_valid.json");
+    std::fs::write(
+        &path,
+        r#"[{"energy": 100.0, "arrival_time": 1234567890.123, "error": 0.001},
+            {"energy": 200.0, "arrival_time": 1234567891.456, "error": 0.002}]"#,
+    )
+    .unwrap();
+
+    let data = JsonImporter::new().load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_same(&sample_data(), &data);
+}
+
+#[test]
+fn test_json_importer_empty_array() {
+    let path = temp_path("import_empty.json");
+    std::fs::write(&path, "[]").unwrap();
+
+    let data = JsonImporter::new().load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert!(data.is_empty());
+}
+
+#[test]
+fn test_json_importer_missing_field() {
+    let path = temp_path("import_missing.json");
+    std::fs::write(&path, r#"[{"energy": 100.0, "arrival_time": 1234567890.123}]"#).unwrap();
+
+    let err = JsonImporter::new().load_from_file(&path).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    let message = err.to_string();
+    assert!(message.contains("missing field `error`"), "unexpected message: {}", message);
+    assert!(message.contains("import_missing.json"), "unexpected message: {}", message);
+}
+
+#[test]
+fn test_ascii_importer_skips_comments_and_blank_lines() {
+    let path = temp_path("ascii_comments.txt");
+    std::fs::write(
+        &path,
+        "# energy time error\n\n100.0 1234567890.123 0.001\n  # indented comment\n200.0 1234567891.456 0.002\n",
+    )
+    .unwrap();
+
+    let data = AsciiImporter::new().load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_same(&sample_data(), &data);
+}
+
+#[test]
+fn test_ascii_importer_ignores_trailing_columns_and_ragged_spacing() {
+    let path = temp_path("ascii_ragged.txt");
+    std::fs::write(
+        &path,
+        "100.0\t 1234567890.123   0.001  FERMI  7\n   200.0 1234567891.456\t\t0.002 extra\n",
+    )
+    .unwrap();
+
+    let data = AsciiImporter::default().load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_same(&sample_data(), &data);
+}
+
+#[test]
+fn test_ascii_importer_custom_columns() {
+    let path = temp_path("ascii_columns.txt");
+    std::fs::write(&path, "0.001 1234567890.123 100.0\n0.002 1234567891.456 200.0\n").unwrap();
+
+    let importer = AsciiImporter::new().with_columns(2, 1, 0);
+    let data = importer.load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_same(&sample_data(), &data);
+}
+
+#[test]
+fn test_ascii_importer_missing_column() {
+    let path = temp_path("ascii_short.txt");
+    std::fs::write(&path, "# header\n100.0 1234567890.123\n").unwrap();
+
+    let err = AsciiImporter::new().load_from_file(&path).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(err, DataError::MissingColumn { line: 2, column: 2 }), "got {:?}", err);
+}
+
+#[test]
+fn test_csv_importer_valid_file() {
+    let path = temp_path("csv_valid.csv");
+    std::fs::write(
+        &path,
+        "energy,arrival_time,error\n100.0,1234567890.123,0.001\n200.0,1234567891.456,0.002\n",
+    )
+    .unwrap();
+
+    let data = CsvImporter::new().load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_same(&sample_data(), &data);
+}
+
+#[test]
+fn test_csv_importer_reports_parse_line_and_column() {
+    let path = temp_path("csv_parse.csv");
+    std::fs::write(
+        &path,
+        "energy,arrival_time,error\n100.0,1234567890.123,0.001\n200.0,not-a-time,0.002\n",
+    )
+    .unwrap();
+
+    let err = CsvImporter::new().load_from_file(&path).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(err, DataError::Parse { line: 3, column: 1 }), "got {:?}", err);
+}
+
+#[test]
+fn test_csv_importer_reports_ragged_row() {
+    let path = temp_path("csv_ragged.csv");
+    std::fs::write(&path, "energy,arrival_time,error\n100.0,1234567890.123\n").unwrap();
+
+    let err = CsvImporter::new().load_from_file(&path).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(err, DataError::InvalidValue { line: 2, .. }), "got {:?}", err);
+}
+
+#[test]
+fn test_csv_importer_missing_file_is_io_error() {
+    let err = CsvImporter::new().load_from_file(&temp_path("does_not_exist.csv")).unwrap_err();
+    assert!(matches!(err, DataError::Io(_)), "got {:?}", err);
+}
+
+/// Analyzer with photons 5σ early and 4σ late against a 1e12 m source
+fn anomalous_analyzer() -> GammaRayAnalyzer {
+    let distance = 1e12;
+    let travel_time = distance / lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurement_with_distance(1.0, travel_time, 1.0, distance);
+    analyzer.add_measurement_with_distance(2.0, travel_time - 5.0, 1.0, distance);
+    analyzer.add_measurement_with_distance(3.0, travel_time + 4.0, 1.0, distance);
+    analyzer
+}
+
+#[test]
+fn test_anomaly_exporter_csv_round_trip() {
+    let anomalies = anomalous_analyzer().detect_anomalies();
+    assert_eq!(anomalies.len(), 2);
+
+    let path = temp_path("anomalies.csv");
+    assert_eq!(AnomalyExporter::for_path(&path), AnomalyExporter::Csv);
+    AnomalyExporter::Csv.save_to_file(&anomalies, &path).unwrap();
+    let mut reader = csv::Reader::from_path(&path).unwrap();
+    let headers = reader.headers().unwrap().clone();
+    assert_eq!(
+        headers.iter().collect::<Vec<_>>(),
+        vec!["energy", "measured_time", "expected_time", "deviation", "significance"]
+    );
+    let significances: Vec<f64> = reader
+        .records()
+        .map(|record| record.unwrap()[4].parse().unwrap())
+        .collect();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(significances.len(), anomalies.len());
+    for (read, anomaly) in significances.iter().zip(&anomalies) {
+        assert_eq!(*read, anomaly.significance);
+    }
+}
+
+#[test]
+fn test_anomaly_exporter_json() {
+    let anomalies = anomalous_analyzer().detect_anomalies();
+    let path = temp_path("anomalies.JSON");
+    let exporter = AnomalyExporter::for_path(&path);
+    assert_eq!(exporter, AnomalyExporter::Json);
+    exporter.save_to_file(&anomalies, &path).unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    let records = json.as_array().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["energy"], 2.0);
+    assert!((records[0]["significance"].as_f64().unwrap() - 5.0).abs() < 1e-6);
+    assert_eq!(records[1]["kind"], "LateArrival");
+}
+
+#[test]
+fn test_result_exporter_writes_result_keys() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurements(&sample_data());
+    let result = analyzer.test_light_speed_constancy();
+    let anomalies = analyzer.detect_anomalies();
+
+    let path = temp_path("result.json");
+    ResultExporter::new()
+        .save_to_file(&result, &anomalies, analyzer.measurements(), &path)
+        .unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert!(json["result"].get("p_value").is_some());
+    assert!(json["result"].get("anomalies_detected").is_some());
+    assert!(json["result"].get("chi_squared").is_some());
+    assert!(json["anomalies"].is_array());
+    assert!(json.get("measurements").is_none());
+    assert!(json["config"].is_null());
+}
+
+#[test]
+fn test_result_exporter_writes_version_envelope() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurements(&sample_data());
+    analyzer.set_significance_level(0.01);
+    let result = analyzer.test_light_speed_constancy();
+
+    let path = temp_path("result_envelope.json");
+    let exporter = ResultExporter { include_measurements: false, config: Some(analyzer.config()) };
+    exporter.save_to_file(&result, &[], analyzer.measurements(), &path).unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(json["tool"], "lightspeedvalidator");
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(json["config"].is_object());
+    assert_eq!(json["config"]["significance_level"], 0.01);
+    assert_eq!(json["result"]["chi_squared"], result.chi_squared);
+}
+
+#[test]
+fn test_result_exporter_can_include_measurements() {
+    let mut analyzer = GammaRayAnalyzer::new();
+    analyzer.add_measurements(&sample_data());
+    let result = analyzer.test_light_speed_constancy();
+
+    let path = temp_path("result_with_input.json");
+    let exporter = ResultExporter { include_measurements: true, config: None };
+    exporter.save_to_file(&result, &[], analyzer.measurements(), &path).unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert!(json["result"].get("p_value").is_some());
+    let measurements: Vec<TimingData> = serde_json::from_value(json["measurements"].clone()).unwrap();
+    assert_same(&sample_data(), &measurements);
+}
+
+#[test]
+fn test_data_format_from_path() {
+    use lightspeedvalidator::DataFormat;
+    use std::path::Path;
+
+    assert_eq!(DataFormat::from_path(Path::new("events.csv")).unwrap(), DataFormat::Csv);
+    assert_eq!(DataFormat::from_path(Path::new("events.Json")).unwrap(), DataFormat::Json);
+    assert_eq!(DataFormat::from_path(Path::new("events.txt")).unwrap(), DataFormat::Ascii);
+    assert_eq!(DataFormat::from_path(Path::new("events.dat")).unwrap(), DataFormat::Ascii);
+    assert!(matches!(
+        DataFormat::from_path(Path::new("events")),
+        Err(DataError::UnknownFormat { .. })
+    ));
+}
+
+#[test]
+fn test_csv_load_from_reader() {
+    use std::io::Cursor;
+
+    let text = "energy,arrival_time,error\n100,1234567890.123,0.001\n200,1234567891.456,0.002\n";
+    let data = CsvImporter::new().load_from_reader(&mut Cursor::new(text.as_bytes())).unwrap();
+    assert_same(&data, &sample_data());
+}
+
+#[test]
+fn test_csv_skip_and_report_collects_bad_rows() {
+    use std::io::Cursor;
+
+    let mut text = String::from("energy,arrival_time,error\n");
+    for i in 0..10 {
+        match i {
+            3 => text.push_str("40,not_a_time,0.001\n"),
+            7 => text.push_str("80,1000.7\n"),
+            _ => text.push_str(&format!("{},{},0.001\n", 10 * (i + 1), 1000.0 + i as f64 / 10.0)),
+        }
+    }
+
+    let importer = CsvImporter::new().with_error_policy(ErrorPolicy::SkipAndReport);
+    let (data, report) = importer.load_with_report(&mut Cursor::new(text.as_bytes())).unwrap();
+    assert_eq!(data.len(), 8);
+    assert!(data.iter().all(|m| m.energy != 40.0 && m.energy != 80.0));
+    // Line 1 is the header, so row i is on line i + 2
+    let lines: Vec<usize> = report.skipped.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, vec![5, 9]);
+    assert!(report.skipped[0].1.contains("column 1"));
+
+    // The default policy still aborts at the first bad row
+    let err = CsvImporter::new().load_from_reader(&mut Cursor::new(text.as_bytes())).unwrap_err();
+    assert!(matches!(err, DataError::Parse { line: 5, column: 1 }), "got {:?}", err);
+}
+
+#[test]
+fn test_json_load_from_reader_reports_stdin_path() {
+    use std::io::Cursor;
+
+    let err = JsonImporter::new().load_from_reader(&mut Cursor::new(&b"[{"[..])).unwrap_err();
+    match err {
+        DataError::Json { path, .. } => assert_eq!(path, PathBuf::from("-")),
+        other => panic!("expected JSON error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_csv_importer_tab_delimited() {
+    let path = temp_path("tabs.tsv");
+    std::fs::write(&path, "energy\tarrival_time\terror\n100\t1234567890.123\t0.001\n200\t1234567891.456\t0.002\n").unwrap();
+    let data = CsvImporter::new().with_delimiter(b'\t').load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_same(&data, &sample_data());
+}
+
+#[test]
+fn test_csv_importer_headerless_reads_first_row() {
+    let path = temp_path("headerless.csv");
+    std::fs::write(&path, "100;1234567890.123;0.001\n200;1234567891.456;0.002\n").unwrap();
+    let importer = CsvImporter::new().with_delimiter(b';').with_headers(false);
+    let data = importer.load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_same(&data, &sample_data());
+}
+
+#[test]
+fn test_csv_importer_defaults_skip_header() {
+    let importer = CsvImporter::default();
+    assert_eq!(importer.delimiter, b',');
+    assert!(importer.has_headers);
+}
+
+#[test]
+fn test_csv_importer_column_map_reorders_and_renames() {
+    let path = temp_path("named.csv");
+    std::fs::write(
+        &path,
+        "sigma_t,detector,t_obs,energy_gev\n0.001,LAT,1234567890.123,100\n0.002,LAT,1234567891.456,200\n",
+    )
+    .unwrap();
+    let importer = CsvImporter::new().with_column_map(ColumnMap::new("energy_gev", "t_obs", "sigma_t"));
+    let data = importer.load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_same(&data, &sample_data());
+}
+
+#[test]
+fn test_importers_read_optional_weight_column() {
+    let path = temp_path("weighted.csv");
+    std::fs::write(
+        &path,
+        "energy,arrival_time,error,weight\n100,1234567890.123,0.001,0.5\n200,1234567891.456,0.002,2\n",
+    )
+    .unwrap();
+    let data = CsvImporter::new().with_weight_column(3).load_from_file(&path).unwrap();
+    assert_eq!(data[0].weight, Some(0.5));
+    assert_eq!(data[1].weight, Some(2.0));
+    assert!(CsvImporter::new().load_from_file(&path).unwrap().iter().all(|m| m.weight.is_none()));
+
+    let importer = CsvImporter::new()
+        .with_column_map(ColumnMap::new("energy", "arrival_time", "error").with_weight("weight"));
+    assert_eq!(importer.load_from_file(&path).unwrap()[0].weight, Some(0.5));
+    std::fs::remove_file(&path).unwrap();
+
+    let data = AsciiImporter::new()
+        .with_weight_column(3)
+        .load_from_reader(&mut "100 1234567890.123 0.001 0.25\n200 1234567891.456 0.002\n".as_bytes())
+        .unwrap();
+    assert_eq!(data[0].weight, Some(0.25));
+    assert_eq!(data[1].weight, None);
+
+    let json = r#"[{"energy": 100.0, "arrival_time": 1234567890.123, "error": 0.001, "weight": 2.0}]"#;
+    let data = JsonImporter::new().load_from_reader(&mut json.as_bytes()).unwrap();
+    assert_eq!(data[0].weight, Some(2.0));
+}
+
+#[test]
+fn test_importers_read_optional_geometry_columns() {
+    use lightspeedvalidator::NdjsonImporter;
+
+    let path = temp_path("geometry.csv");
+    std::fs::write(
+        &path,
+        "energy,arrival_time,error,distance_m,redshift\n\
+         100,1234567890.123,0.001,1e25,0.9\n\
+         200,1234567891.456,0.002,2e25,1.5\n",
+    )
+    .unwrap();
+    let importer = CsvImporter::new().with_distance_column(3).with_redshift_column(4);
+    let data = importer.load_from_file(&path).unwrap();
+    assert_eq!((data[0].distance_m, data[0].redshift), (Some(1e25), Some(0.9)));
+    assert_eq!((data[1].distance_m, data[1].redshift), (Some(2e25), Some(1.5)));
+
+    let map = ColumnMap::new("energy", "arrival_time", "error").with_distance("distance_m");
+    let data = CsvImporter::new().with_column_map(map).load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(data[0].distance_m, Some(1e25));
+    assert_eq!(data[0].redshift, None);
+
+    // A file without the columns leaves the fields unset
+    let path = temp_path("no_geometry.csv");
+    CsvExporter.save_to_file(&sample_data(), &path).unwrap();
+    let data = importer.load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_same(&data, &sample_data());
+    assert!(data.iter().all(|m| m.distance_m.is_none() && m.redshift.is_none()));
+
+    let json = r#"[{"energy": 100.0, "arrival_time": 12.5, "error": 0.001, "distance_m": 1e25},
+                   {"energy": 200.0, "arrival_time": 13.5, "error": 0.001}]"#;
+    let data = JsonImporter::new().load_from_reader(&mut json.as_bytes()).unwrap();
+    assert_eq!(data[0].distance_m, Some(1e25));
+    assert_eq!(data[1].distance_m, None);
+
+    let lines = r#"{"energy": 100.0, "arrival_time": 12.5, "error": 0.001, "redshift": 0.9}
+{"energy": 200.0, "arrival_time": 13.5, "error": 0.001}
+"#;
+    let data = NdjsonImporter::new().load_from_reader(&mut lines.as_bytes()).unwrap();
+    assert_eq!(data[0].redshift, Some(0.9));
+    assert_eq!(data[1].redshift, None);
+}
+
+#[test]
+fn test_csv_exporter_writes_weight_column_only_when_weighted() {
+    let path = temp_path("weighted_export.csv");
+    let mut data = sample_data();
+    CsvExporter.save_to_file(&data, &path).unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().starts_with("energy,arrival_time,error\n"));
+
+    data[1].weight = Some(0.5);
+    CsvExporter.save_to_file(&data, &path).unwrap();
+    let reloaded = CsvImporter::new().with_weight_column(3).load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(reloaded[0].weight, Some(1.0));
+    assert_eq!(reloaded[1].weight, Some(0.5));
+}
+
+#[test]
+fn test_csv_exporter_round_trips_source_geometry() {
+    let path = temp_path("geometry_export.csv");
+    let mut data = sample_data();
+    data[0].distance_m = Some(1e25);
+    data[1].redshift = Some(0.9);
+    CsvExporter.save_to_file(&data, &path).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(text.starts_with("energy,arrival_time,error,distance_m,redshift\n"), "{}", text);
+
+    let map = ColumnMap::new("energy", "arrival_time", "error")
+        .with_distance("distance_m")
+        .with_redshift("redshift");
+    let reloaded = CsvImporter::new().with_column_map(map).load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_same(&reloaded, &data);
+    assert_eq!((reloaded[0].distance_m, reloaded[0].redshift), (Some(1e25), None));
+    assert_eq!((reloaded[1].distance_m, reloaded[1].redshift), (None, Some(0.9)));
+}
+
+#[test]
+fn test_csv_importer_column_map_missing_name() {
+    let path = temp_path("named_missing.csv");
+    std::fs::write(&path, "energy_gev,t_obs\n100,1234567890.123\n").unwrap();
+    let importer = CsvImporter::new().with_column_map(ColumnMap::new("energy_gev", "t_obs", "sigma_t"));
+    let err = importer.load_from_file(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    match err {
+        DataError::MissingHeader { name } => assert_eq!(name, "sigma_t"),
+        other => panic!("expected missing header, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_csv_importer_column_map_requires_headers() {
+    let path = temp_path("named_headerless.csv");
+    std::fs::write(&path, "100,1234567890.123,0.01\n").unwrap();
+    let importer = CsvImporter::new()
+        .with_headers(false)
+        .with_column_map(ColumnMap::new("energy_gev", "t_obs", "sigma_t"));
+    let err = importer.load_from_file(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    match err {
+        DataError::InvalidValue { reason, .. } => assert!(reason.contains("header row")),
+        other => panic!("expected invalid configuration, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_energy_unit_conversion() {
+    assert_eq!(EnergyUnit::TeV.to_gev(1.0), 1000.0);
+    assert!((EnergyUnit::MeV.to_gev(500.0) - 0.5).abs() < 1e-12);
+    assert_eq!(EnergyUnit::GeV.to_gev(42.0), 42.0);
+    assert_eq!(EnergyUnit::default(), EnergyUnit::GeV);
+}
+
+#[test]
+fn test_importers_normalize_energy_units() {
+    use std::io::Cursor;
+
+    let csv = "energy,arrival_time,error\n1,10.0,0.1\n";
+    let data = CsvImporter::new()
+        .with_energy_unit(EnergyUnit::TeV)
+        .load_from_reader(&mut Cursor::new(csv.as_bytes()))
+        .unwrap();
+    assert_eq!(data[0].energy, 1000.0);
+
+    let json = r#"[{"energy": 500.0, "arrival_time": 10.0, "error": 0.1}]"#;
+    let data = JsonImporter::new()
+        .with_energy_unit(EnergyUnit::MeV)
+        .load_from_reader(&mut Cursor::new(json.as_bytes()))
+        .unwrap();
+    assert!((data[0].energy - 0.5).abs() < 1e-12);
+
+    let ascii = "1 10.0 0.1\n";
+    let data = AsciiImporter::new()
+        .with_energy_unit(EnergyUnit::TeV)
+        .load_from_reader(&mut Cursor::new(ascii.as_bytes()))
+        .unwrap();
+    assert_eq!(data[0].energy, 1000.0);
+}
+
+#[test]
+fn test_streaming_matches_in_memory_analysis() {
+    use lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+
+    let distance = 1.0e12;
+    let travel_time = distance / SPEED_OF_LIGHT;
+    let path = temp_path("stream.csv");
+    let data: Vec<TimingData> = (0..200)
+        .map(|i| {
+            let scatter = ((i * 37) % 11) as f64 * 1e-3 - 5e-3;
+            let outlier = if i == 77 { 0.5 } else { 0.0 };
+            TimingData::new(1.0 + i as f64, travel_time + scatter + outlier, 0.01)
+        })
+        .collect();
+    CsvExporter.save_to_file(&data, &path).unwrap();
+
+    let loaded: Vec<TimingData> = CsvImporter::new()
+        .load_from_file(&path)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.with_distance(distance))
+        .collect();
+    let mut in_memory = GammaRayAnalyzer::new();
+    in_memory.add_measurements(&loaded);
+    let expected = in_memory.test_light_speed_constancy();
+    assert!(expected.chi_squared > 0.0);
+    assert_eq!(expected.anomalies_detected, 1);
+
+    let mut streaming = GammaRayAnalyzer::new();
+    let mut rows = 0;
+    CsvImporter::new()
+        .stream(&path, &mut |measurement| {
+            streaming.accumulate(&measurement.with_distance(distance));
+            rows += 1;
+        })
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let result = streaming.finalize_streaming();
+
+    assert_eq!(rows, data.len());
+    assert!(streaming.is_empty());
+    assert_eq!(result.chi_squared, expected.chi_squared);
+    assert_eq!(result.p_value, expected.p_value);
+    assert_eq!(result.deviation_estimate, expected.deviation_estimate);
+    assert_eq!(result.deviation_error, expected.deviation_error);
+    assert_eq!(result.anomalies_detected, expected.anomalies_detected);
+    assert_eq!(result.is_valid, expected.is_valid);
+}
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+#[test]
+fn test_data_format_skips_gz_extension() {
+    use lightspeedvalidator::DataFormat;
+
+    assert_eq!(DataFormat::from_path(&fixture("sample.csv.gz")).unwrap(), DataFormat::Csv);
+    let json = std::path::Path::new("events.json.GZ");
+    assert_eq!(DataFormat::from_path(json).unwrap(), DataFormat::Json);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_csv_matches_uncompressed_twin() {
+    let plain = CsvImporter::new().load_from_file(&fixture("sample.csv")).unwrap();
+    let compressed = CsvImporter::new().load_from_file(&fixture("sample.csv.gz")).unwrap();
+    assert_same(&plain, &sample_data());
+    assert_same(&compressed, &plain);
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn test_gzip_input_requires_feature() {
+    let err = CsvImporter::new().load_from_file(&fixture("sample.csv.gz")).unwrap_err();
+    assert!(matches!(err, DataError::Compressed { .. }));
+}
+
+#[test]
+fn test_ndjson_importer_reads_lines() {
+    use lightspeedvalidator::NdjsonImporter;
+
+    let path = temp_path("events.ndjson");
+    std::fs::write(
+        &path,
+        "{\"energy\": 100.0, \"arrival_time\": 1234567890.123, \"error\": 0.001}\n\
+         \n\
+         {\"energy\": 200.0, \"arrival_time\": 1234567891.456, \"error\": 0.002}\n",
+    )
+    .unwrap();
+    let data = NdjsonImporter::new().load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_same(&data, &sample_data());
+}
+
+#[test]
+fn test_ndjson_importer_reports_malformed_line() {
+    use lightspeedvalidator::NdjsonImporter;
+
+    let path = temp_path("malformed.ndjson");
+    std::fs::write(
+        &path,
+        "{\"energy\": 1.0, \"arrival_time\": 0.0, \"error\": 0.1}\n\
+         {\"energy\": 2.0, \"arrival_time\": 0.0, \"error\": 0.1}\n\
+         {\"energy\": 3.0, \"arrival_time\": \n",
+    )
+    .unwrap();
+    let err = NdjsonImporter::new().load_from_file(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    match &err {
+        DataError::InvalidValue { line, .. } => assert_eq!(*line, 3),
+        other => panic!("expected invalid value, got {:?}", other),
+    }
+    assert!(err.to_string().starts_with("line 3:"));
+}
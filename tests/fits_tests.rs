@@ -0,0 +1,78 @@
+#![cfg(feature = "fits")]
+
+use lightspeedvalidator::fits::FERMI_MET_EPOCH_UNIX;
+use lightspeedvalidator::{
+    DataExporter, DataImporter, EnergyUnit, FitsExporter, FitsImporter, TimingData,
+};
+use std::path::Path;
+
+fn fixture() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/events.fits"))
+}
+
+#[test]
+fn test_fits_importer_reads_event_table() {
+    let data = FitsImporter::new().load_from_file(fixture()).unwrap();
+    let expected = [(0.1, 1000.0, 0.001), (0.2, 1001.5, 0.002), (1.5, 1003.25, 0.004)];
+    assert_eq!(data.len(), expected.len());
+    // Energies are stored in MeV and converted to GeV
+    for (m, (energy, time, error)) in data.iter().zip(expected) {
+        assert!((m.energy - energy).abs() < 1e-12);
+        assert_eq!(m.arrival_time, time);
+        assert_eq!(m.error, error);
+    }
+}
+
+#[test]
+fn test_fits_importer_applies_met_epoch() {
+    let importer = FitsImporter::new().with_time_offset(FERMI_MET_EPOCH_UNIX);
+    let data = importer.load_from_file(fixture()).unwrap();
+    assert_eq!(data[0].arrival_time, FERMI_MET_EPOCH_UNIX + 1000.0);
+}
+
+#[test]
+fn test_fits_importer_missing_column_is_error() {
+    let importer = FitsImporter::new().with_columns("ENERGY", "TIME", "NO_SUCH_COLUMN");
+    assert!(importer.load_from_file(fixture()).is_err());
+}
+
+#[test]
+fn test_fits_exporter_round_trips_measurements() {
+    let data = vec![
+        TimingData::new(0.1, 1000.0, 0.001),
+        TimingData::new(2.5, 1001.5, 0.002),
+        TimingData::new(40.0, 1003.25, 0.004),
+    ];
+    let path = std::env::temp_dir().join(format!("lsv_export_{}.fits", std::process::id()));
+    FitsExporter::new().save_to_file(&data, &path).unwrap();
+    
+    let importer = FitsImporter::new()
+        .with_columns("ENERGY", "TIME", "ERROR")
+        .with_energy_unit(EnergyUnit::GeV);
+    let loaded = importer.load_from_file(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    
+    assert_eq!(loaded.len(), data.len());
+    for (m, original) in loaded.iter().zip(&data) {
+        assert_eq!(m.energy, original.energy);
+        assert_eq!(m.arrival_time, original.arrival_time);
+        assert_eq!(m.error, original.error);
+    }
+}
+
+#[test]
+fn test_fits_importer_reads_concurrent_streams() {
+    let bytes = std::fs::read(fixture()).unwrap();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let bytes = bytes.clone();
+            std::thread::spawn(move || {
+                FitsImporter::new().load_from_reader(&mut bytes.as_slice()).unwrap().len()
+            })
+        })
+        .collect();
+    // Each load spools to its own temporary file
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 3);
+    }
+}
@@ -1,20 +1,21 @@
-tests/integration_tests.rs
 
-use lightspeedvalidator::{GammaRayAnalyzer, TimingData, LightSpeedTestResult};
+use lightspeedvalidator::GammaRayAnalyzer;
 
 #[test]
 fn test_basic_analyzer_creation() {
     let analyzer = GammaRayAnalyzer::new();
-    assert_eq!(analyzer.measurements.len(), 0);
+    assert_eq!(analyzer.len(), 0);
+    assert!(analyzer.is_empty());
+    assert!(analyzer.measurements().is_empty());
 }
 
 #[test]
 fn test_add_measurement() {
     let mut analyzer = GammaRayAnalyzer::new();
     analyzer.add_measurement(100.0, 1234567890.123, 0.001);
-    assert_eq!(analyzer.measurements.len(), 1);
+    assert_eq!(analyzer.len(), 1);
     
-    let measurement = &analyzer.measurements[0];
+    let measurement = &analyzer.measurements()[0];
     assert_eq!(measurement.energy, 100.0);
     assert_eq!(measurement.arrival_time, 1234567890.123);
     assert_eq!(measurement.error, 0.001);
@@ -23,10 +24,10 @@ fn test_add_measurement() {
 #[test]
 fn test_set_sensitivity_threshold() {
     let mut analyzer = GammaRayAnalyzer::new();
-    assert_eq!(analyzer.sensitivity_threshold, 1e-12);
+    assert_eq!(analyzer.sensitivity_threshold(), 1e-12);
     
     analyzer.set_sensitivity_threshold(1e-10);
-    assert_eq!(analyzer.sensitivity_threshold, 1e-10);
+    assert_eq!(analyzer.sensitivity_threshold(), 1e-10);
 }
 
 #[test]
@@ -35,11 +36,9 @@ fn test_light_speed_test_result_structure() {
     let result = analyzer.test_light_speed_constancy();
     
     // Verify the result has the expected structure
-    assert!(result.is_valid || !result.is_valid); // Valid boolean value
     assert!(result.confidence_level >= 0.0 && result.confidence_level <= 1.0);
     assert!(result.chi_squared >= 0.0);
     assert!(result.p_value >= 0.0 && result.p_value <= 1.0);
-    assert!(result.anomalies_detected >= 0);
 }
 
 #[test]
@@ -69,12 +68,12 @@ fn test_full_workflow() {
     let expected_arrivals = analyzer.calculate_expected_arrivals();
     assert_eq!(expected_arrivals.len(), 3);
     
-    // Test that we can detect anomalies (will likely be empty with no real deviations)
+    // Without geometry every photon is expected at the earliest arrival, so the two
+    // later photons, seconds behind with millisecond errors, stand out
     let anomalies = analyzer.detect_anomalies();
-    assert!(anomalies.len() >= 0);
+    assert_eq!(anomalies.len(), 2);
     
     // Test the full analysis
     let result = analyzer.test_light_speed_constancy();
-    assert!(result.is_valid || !result.is_valid);
     assert!(result.confidence_level >= 0.0 && result.confidence_level <= 1.0);
 }
\ No newline at end of file
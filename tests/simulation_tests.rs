@@ -0,0 +1,147 @@
+use lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+use lightspeedvalidator::{
+    simulate, CsvExporter, CsvImporter, DataExporter, DataImporter, DispersionOrder,
+    PowerLawSpectrum, SimulationConfig, SpeedOfLightHypothesisTest, TimingData,
+};
+
+fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("lsv_{}_{}", std::process::id(), name))
+}
+
+/// Weighted least-squares slope of arrival time against energy and its standard error
+fn fit_slope(data: &[TimingData]) -> (f64, f64) {
+    let (mut s, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for m in data {
+        let w = 1.0 / (m.error * m.error);
+        s += w;
+        sx += w * m.energy;
+        sy += w * m.arrival_time;
+        sxx += w * m.energy * m.energy;
+        sxy += w * m.energy * m.arrival_time;
+    }
+    let delta = s * sxx - sx * sx;
+    ((s * sxy - sx * sy) / delta, (s / delta).sqrt())
+}
+
+fn injected_config() -> SimulationConfig {
+    SimulationConfig {
+        photon_count: 2000,
+        energy_min: 0.1,
+        energy_max: 100.0,
+        spectral_index: 2.0,
+        distance_m: Some(SPEED_OF_LIGHT * 1.0e17),
+        redshift: None,
+        energy_scale: 1.0e19,
+        dispersion_order: DispersionOrder::Linear,
+        noise_sigma: 0.01,
+        seed: Some(42),
+    }
+}
+
+#[test]
+fn test_simulate_respects_configuration() {
+    let config = injected_config();
+    let data = simulate(&config).unwrap();
+    assert_eq!(data.len(), config.photon_count);
+    for m in &data {
+        assert!(m.energy >= config.energy_min && m.energy <= config.energy_max);
+        assert_eq!(m.error, config.noise_sigma);
+    }
+}
+
+#[test]
+fn test_simulate_is_reproducible_with_seed() {
+    let config = injected_config();
+    let a = simulate(&config).unwrap();
+    let b = simulate(&config).unwrap();
+    for (x, y) in a.iter().zip(&b) {
+        assert_eq!(x.energy, y.energy);
+        assert_eq!(x.arrival_time, y.arrival_time);
+    }
+}
+
+#[test]
+fn test_simulate_requires_geometry() {
+    let config = SimulationConfig { distance_m: None, redshift: None, ..injected_config() };
+    assert!(simulate(&config).is_err());
+}
+
+#[test]
+fn test_simulate_and_reanalyze_recovers_injected_slope() {
+    let config = injected_config();
+    let path = temp_path("simulated.csv");
+    CsvExporter.save_to_file(&simulate(&config).unwrap(), &path).unwrap();
+    let data = CsvImporter::new().load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(data.len(), config.photon_count);
+
+    // Linear dispersion: Δt = (E / E_QG) · d/c, i.e. 0.01 s per GeV here
+    let injected_slope = config.travel_time().unwrap() / config.energy_scale;
+    let (slope, slope_error) = fit_slope(&data);
+    assert!(
+        (slope - injected_slope).abs() < 5.0 * slope_error,
+        "recovered {} ± {}, injected {}",
+        slope,
+        slope_error,
+        injected_slope
+    );
+
+    // The injected dispersion is far above the noise, so constant c is rejected
+    assert!(SpeedOfLightHypothesisTest::chi_squared_test(&data).is_significant);
+}
+
+#[test]
+fn test_simulate_without_dispersion_is_consistent_with_constant_c() {
+    let config = SimulationConfig { energy_scale: f64::INFINITY, ..injected_config() };
+    let data = simulate(&config).unwrap();
+    let (slope, slope_error) = fit_slope(&data);
+    assert!(slope.abs() < 5.0 * slope_error);
+}
+
+/// Photon index from a least-squares fit of log density against log energy, using
+/// `bins` logarithmic bins between the spectrum bounds
+fn fitted_index(energies: &[f64], spectrum: &PowerLawSpectrum, bins: usize) -> f64 {
+    let log_span = (spectrum.e_max / spectrum.e_min).ln();
+    let mut counts = vec![0usize; bins];
+    for &energy in energies {
+        let bin = ((energy / spectrum.e_min).ln() / log_span * bins as f64) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+    let points: Vec<(f64, f64)> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            let low = spectrum.e_min * (log_span * i as f64 / bins as f64).exp();
+            let high = spectrum.e_min * (log_span * (i + 1) as f64 / bins as f64).exp();
+            ((low * high).sqrt().ln(), (count as f64 / (high - low)).ln())
+        })
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    -sxy / sxx
+}
+
+#[test]
+fn test_power_law_spectrum_recovers_requested_index() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    for index in [1.0, 1.5, 2.0, 2.5] {
+        let spectrum = PowerLawSpectrum { index, e_min: 0.1, e_max: 100.0 };
+        let mut rng = StdRng::seed_from_u64(9);
+        let energies: Vec<f64> = (0..200_000).map(|_| spectrum.sample(&mut rng)).collect();
+        assert!(energies.iter().all(|&e| (spectrum.e_min..=spectrum.e_max).contains(&e)));
+        let fitted = fitted_index(&energies, &spectrum, 15);
+        assert!((fitted - index).abs() < 0.05, "index {} fitted as {}", index, fitted);
+    }
+}
+
+#[test]
+fn test_simulation_config_spectrum_uses_configured_index() {
+    let config = SimulationConfig { spectral_index: 2.7, ..injected_config() };
+    let spectrum = config.spectrum();
+    assert_eq!(spectrum, PowerLawSpectrum { index: 2.7, e_min: 0.1, e_max: 100.0 });
+}
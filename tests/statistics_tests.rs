@@ -0,0 +1,593 @@
+use lightspeedvalidator::{chi_squared_pvalue, SpeedOfLightHypothesisTest, TimingData};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "expected {} within {}, got {}",
+        expected,
+        tolerance,
+        actual
+    );
+}
+
+#[test]
+fn test_chi_squared_pvalue_tabulated_values() {
+    // Critical values from standard chi-squared tables
+    assert_close(chi_squared_pvalue(3.841, 1), 0.05, 1e-4);
+    assert_close(chi_squared_pvalue(6.635, 1), 0.01, 1e-4);
+    assert_close(chi_squared_pvalue(5.991, 2), 0.05, 1e-4);
+    assert_close(chi_squared_pvalue(18.307, 10), 0.05, 1e-4);
+    assert_close(chi_squared_pvalue(124.342, 100), 0.05, 1e-4);
+}
+
+#[test]
+fn test_chi_squared_pvalue_matches_closed_form_for_two_dof() {
+    // With two degrees of freedom the survival function is exactly exp(-x/2)
+    for &x in &[0.1, 1.0, 4.0, 10.0, 50.0] {
+        assert_close(chi_squared_pvalue(x, 2), (-x / 2.0_f64).exp(), 1e-12);
+    }
+}
+
+#[test]
+fn test_chi_squared_pvalue_edge_cases() {
+    assert_eq!(chi_squared_pvalue(0.0, 3), 1.0);
+    assert_eq!(chi_squared_pvalue(5.0, 0), 1.0);
+    assert!(chi_squared_pvalue(f64::NAN, 3).is_nan());
+}
+
+#[test]
+fn test_chi_squared_pvalue_large_statistic_is_stable() {
+    let p = chi_squared_pvalue(1000.0, 5);
+    assert!(p.is_finite());
+    assert!((0.0..1e-200).contains(&p));
+
+    let p = chi_squared_pvalue(5000.0, 4000);
+    assert!(p.is_finite());
+    assert!((0.0..1e-10).contains(&p));
+}
+
+#[test]
+fn test_chi_squared_pvalue_is_monotonic_in_statistic() {
+    let mut previous = 1.0;
+    for i in 1..50 {
+        let p = chi_squared_pvalue(i as f64, 7);
+        assert!(p <= previous);
+        previous = p;
+    }
+}
+
+fn measurement(energy: f64, arrival_time: f64, error: f64) -> TimingData {
+    TimingData::new(energy, arrival_time, error)
+}
+
+#[test]
+fn test_chi_squared_test_known_statistic() {
+    // Weighted mean is 1.0, so residuals are -1 and +1 sigma
+    let data = vec![measurement(100.0, 0.0, 1.0), measurement(200.0, 2.0, 1.0)];
+    let result = SpeedOfLightHypothesisTest::chi_squared_test(&data);
+    assert_close(result.chi_squared, 2.0, 1e-12);
+    assert_eq!(result.degrees_of_freedom, 1);
+    assert_close(result.p_value, chi_squared_pvalue(2.0, 1), 1e-12);
+    assert!(!result.is_significant);
+}
+
+#[test]
+fn test_chi_squared_test_applies_photon_weights() {
+    // Weight 3 on the late photon moves the weighted mean to 1.5, giving
+    // χ² = 1 · 1.5² + 3 · 0.5² = 3
+    let data = vec![measurement(100.0, 0.0, 1.0), measurement(200.0, 2.0, 1.0).with_weight(3.0)];
+    let result = SpeedOfLightHypothesisTest::chi_squared_test(&data);
+    assert_close(result.chi_squared, 3.0, 1e-12);
+    assert_eq!(result.degrees_of_freedom, 1);
+}
+
+#[test]
+fn test_chi_squared_test_uses_inverse_variance_weights() {
+    // Weighted mean: (0/1 + 3/4) / (1 + 1/4) = 0.6
+    let data = vec![measurement(100.0, 0.0, 1.0), measurement(200.0, 3.0, 2.0)];
+    let result = SpeedOfLightHypothesisTest::chi_squared_test(&data);
+    let expected = 0.6_f64.powi(2) + (2.4_f64 / 2.0).powi(2);
+    assert_close(result.chi_squared, expected, 1e-12);
+}
+
+#[test]
+fn test_chi_squared_test_flags_significant_scatter() {
+    let data: Vec<TimingData> = (0..10)
+        .map(|i| measurement(100.0 * (i + 1) as f64, i as f64 * 0.1, 0.01))
+        .collect();
+    let result = SpeedOfLightHypothesisTest::chi_squared_test(&data);
+    assert_eq!(result.degrees_of_freedom, 9);
+    assert!(result.is_significant);
+    assert!(result.p_value < 1e-10);
+}
+
+#[test]
+fn test_chi_squared_test_skips_zero_error_points() {
+    let data = vec![
+        measurement(100.0, 0.0, 1.0),
+        measurement(150.0, 50.0, 0.0),
+        measurement(200.0, 2.0, 1.0),
+    ];
+    let result = SpeedOfLightHypothesisTest::chi_squared_test(&data);
+    assert_close(result.chi_squared, 2.0, 1e-12);
+    assert_eq!(result.degrees_of_freedom, 1);
+}
+
+/// Residuals spread deterministically around `center` with equal errors
+fn spread_dataset(center: f64, n: usize) -> Vec<TimingData> {
+    (0..n)
+        .map(|i| {
+            let offset = ((i * 37) % n) as f64 / n as f64 - 0.5;
+            measurement(100.0 + i as f64, center + offset, 0.1)
+        })
+        .collect()
+}
+
+#[test]
+fn test_bootstrap_interval_brackets_true_mean() {
+    let data = spread_dataset(5.0, 200);
+    let interval = SpeedOfLightHypothesisTest::bootstrap_confidence_interval(&data, 1000, Some(7));
+    assert_eq!(interval.method_used, "Bootstrap");
+    assert_eq!(interval.confidence_level, 0.95);
+    assert!(interval.lower_bound < 5.0 && interval.upper_bound > 5.0, "{:?}", interval);
+    // Standard error of the mean is about 0.29 / sqrt(200) ≈ 0.02
+    assert!(interval.upper_bound - interval.lower_bound < 0.2, "{:?}", interval);
+}
+
+#[test]
+fn test_bootstrap_interval_is_reproducible() {
+    let data = spread_dataset(0.0, 50);
+    let a = SpeedOfLightHypothesisTest::bootstrap_confidence_interval(&data, 500, Some(11));
+    let b = SpeedOfLightHypothesisTest::bootstrap_confidence_interval(&data, 500, Some(11));
+    assert_eq!(a.lower_bound, b.lower_bound);
+    assert_eq!(a.upper_bound, b.upper_bound);
+
+    let default_a = SpeedOfLightHypothesisTest::estimate_confidence_intervals(&data);
+    let default_b = SpeedOfLightHypothesisTest::estimate_confidence_intervals(&data);
+    assert_eq!(default_a.lower_bound, default_b.lower_bound);
+    assert_eq!(default_a.upper_bound, default_b.upper_bound);
+}
+
+#[test]
+fn test_bootstrap_seed_controls_resampling() {
+    let data = spread_dataset(0.0, 50);
+    let bits = |seed| {
+        let ci = SpeedOfLightHypothesisTest::bootstrap_confidence_interval(&data, 500, Some(seed));
+        (ci.lower_bound.to_bits(), ci.upper_bound.to_bits())
+    };
+    assert_eq!(bits(42), bits(42));
+    assert_ne!(bits(42), bits(43));
+}
+
+#[test]
+fn test_bayesian_analysis_is_deterministic() {
+    let data = dispersed_dataset(1e-3, 0.01);
+    let a = SpeedOfLightHypothesisTest::bayesian_analysis(&data);
+    let b = SpeedOfLightHypothesisTest::bayesian_analysis(&data);
+    assert_eq!(a.posterior_mean.to_bits(), b.posterior_mean.to_bits());
+    assert_eq!(a.evidence.to_bits(), b.evidence.to_bits());
+}
+
+#[test]
+fn test_bootstrap_interval_empty_input() {
+    let interval = SpeedOfLightHypothesisTest::estimate_confidence_intervals(&[]);
+    assert!(interval.lower_bound.is_nan());
+    assert!(interval.upper_bound.is_nan());
+}
+
+/// Arrival times following `slope · E` with small deterministic scatter
+fn dispersed_dataset(slope: f64, error: f64) -> Vec<TimingData> {
+    (1..=40)
+        .map(|i| {
+            let energy = i as f64 * 2.5;
+            let scatter = if i % 2 == 0 { 0.5 } else { -0.5 } * error;
+            measurement(energy, slope * energy + scatter, error)
+        })
+        .collect()
+}
+
+#[test]
+fn test_bayesian_posterior_separates_from_zero_for_dispersed_data() {
+    let data = dispersed_dataset(0.01, 0.01);
+    let result = SpeedOfLightHypothesisTest::bayesian_analysis(&data);
+    assert_close(result.posterior_mean, 0.01, 1e-4);
+    let (low, high) = result.credible_interval;
+    assert!(low > 0.0, "credible interval {:?} should exclude zero", result.credible_interval);
+    assert!(low < 0.01 && high > 0.01);
+    assert!(result.model_comparison.bayes_factor > 1e6);
+    assert!(result.model_comparison.model_evidence_ratio > 0.0);
+}
+
+#[test]
+fn test_bayesian_posterior_consistent_with_zero_for_null_data() {
+    let data = dispersed_dataset(0.0, 0.01);
+    let result = SpeedOfLightHypothesisTest::bayesian_analysis(&data);
+    let (low, high) = result.credible_interval;
+    assert!(low < 0.0 && high > 0.0, "credible interval {:?}", result.credible_interval);
+    // The extra parameter is penalized by the Occam factor of the wide prior
+    assert!(result.model_comparison.bayes_factor < 1.0);
+}
+
+#[test]
+fn test_bayesian_ignores_absolute_arrival_offset() {
+    let offset = 1.7e9;
+    let shifted = |data: Vec<TimingData>| -> Vec<TimingData> {
+        data.into_iter().map(|m| measurement(m.energy, m.arrival_time + offset, m.error)).collect()
+    };
+
+    // Undispersed photons at Unix-epoch times give no evidence for η ≠ 0
+    let undispersed = shifted(dispersed_dataset(0.0, 0.01));
+    let null = SpeedOfLightHypothesisTest::bayesian_analysis(&undispersed);
+    let (low, high) = null.credible_interval;
+    assert!(low < 0.0 && high > 0.0, "credible interval {:?}", null.credible_interval);
+    assert!(null.model_comparison.bayes_factor < 1.0);
+
+    // A real slope is recovered regardless of the offset
+    let dispersed = dispersed_dataset(0.01, 0.01);
+    let plain = SpeedOfLightHypothesisTest::bayesian_analysis(&dispersed);
+    let result = SpeedOfLightHypothesisTest::bayesian_analysis(&shifted(dispersed));
+    assert_close(result.posterior_mean, 0.01, 1e-4);
+    assert_close(result.posterior_mean, plain.posterior_mean, 1e-6);
+    assert!(result.model_comparison.bayes_factor > 1e6);
+}
+
+#[test]
+fn test_bayesian_evidence_uses_prior_range() {
+    let data = dispersed_dataset(0.01, 0.01);
+    let narrow = SpeedOfLightHypothesisTest::bayesian_analysis_with_prior(&data, (0.0, 0.02), 2001);
+    let wide = SpeedOfLightHypothesisTest::bayesian_analysis_with_prior(&data, (-0.04, 0.06), 20001);
+    assert!(narrow.evidence > 0.0);
+    // Same likelihood mass spread over a prior 5 times wider
+    assert_close(narrow.evidence / wide.evidence, 5.0, 0.05);
+    assert_close(narrow.posterior_mean, wide.posterior_mean, 1e-5);
+}
+
+#[test]
+fn test_hypothesis_test_accepts_null_consistent_data() {
+    // Arrival times scattered by half their errors around a common value
+    let data: Vec<TimingData> = (0..20)
+        .map(|i| measurement(100.0 + i as f64, if i % 2 == 0 { 0.005 } else { -0.005 }, 0.01))
+        .collect();
+    let result = SpeedOfLightHypothesisTest::hypothesis_test(&data, 0.05).unwrap();
+    assert!(!result.is_rejected, "{:?}", result);
+    assert_eq!(result.significance_level, 0.05);
+    assert!(result.p_value > 0.05);
+}
+
+#[test]
+fn test_hypothesis_test_rejects_dispersed_data() {
+    let data = dispersed_dataset(0.01, 0.01);
+    let result = SpeedOfLightHypothesisTest::hypothesis_test(&data, 0.05).unwrap();
+    assert!(result.is_rejected, "{:?}", result);
+    assert!(result.test_statistic > 0.0);
+    assert!(result.p_value < 0.05);
+}
+
+#[test]
+fn test_hypothesis_test_rejects_invalid_alpha() {
+    let data = dispersed_dataset(0.0, 0.01);
+    for alpha in [0.0, 1.0, -0.1, 1.5, f64::NAN] {
+        assert!(SpeedOfLightHypothesisTest::hypothesis_test(&data, alpha).is_err(), "alpha {}", alpha);
+    }
+}
+
+fn seeded_normals(n: usize, seed: u64) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| {
+            let u1: f64 = 1.0 - rng.gen::<f64>();
+            let u2: f64 = rng.gen();
+            (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+        })
+        .collect()
+}
+
+#[test]
+fn test_ks_normality_accepts_gaussian_residuals() {
+    let data: Vec<TimingData> = seeded_normals(300, 3)
+        .into_iter()
+        .enumerate()
+        .map(|(i, z)| measurement(100.0 + i as f64, 0.02 * z, 0.02))
+        .collect();
+    let result = SpeedOfLightHypothesisTest::ks_normality_test(&data);
+    assert!(result.is_normal, "{:?}", result);
+    assert!(result.statistic < 0.08);
+}
+
+#[test]
+fn test_ks_normality_rejects_skewed_residuals() {
+    // Exponentially distributed delays are strongly right-skewed
+    let mut rng = StdRng::seed_from_u64(5);
+    let data: Vec<TimingData> = (0..300)
+        .map(|i| {
+            let u: f64 = 1.0 - rng.gen::<f64>();
+            measurement(100.0 + i as f64, -u.ln(), 1.0)
+        })
+        .collect();
+    let result = SpeedOfLightHypothesisTest::ks_normality_test(&data);
+    assert!(!result.is_normal, "{:?}", result);
+    assert!(result.p_value < 0.05);
+}
+
+#[test]
+fn test_ks_normality_empty_input() {
+    let result = SpeedOfLightHypothesisTest::ks_normality_test(&[]);
+    assert_eq!(result.statistic, 0.0);
+    assert!(result.is_normal);
+}
+
+#[test]
+fn test_shapiro_wilk_matches_reference_example() {
+    // Shapiro & Wilk's (1965) weights of eleven men, W ≈ 0.79 and p < 0.01
+    let weights = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+    let result = SpeedOfLightHypothesisTest::shapiro_wilk(&weights);
+    assert_close(result.w, 0.79, 0.01);
+    assert!(result.p_value < 0.01, "{:?}", result);
+}
+
+#[test]
+fn test_shapiro_wilk_accepts_gaussian_samples() {
+    for (n, seed) in [(8, 5), (20, 5), (50, 3), (2000, 7)] {
+        let result = SpeedOfLightHypothesisTest::shapiro_wilk(&seeded_normals(n, seed));
+        assert!(result.w > 0.95 && result.w <= 1.0, "n {}: {:?}", n, result);
+        assert!(result.p_value > 0.5, "n {}: {:?}", n, result);
+    }
+}
+
+#[test]
+fn test_shapiro_wilk_rejects_uniform_samples() {
+    let mut rng = StdRng::seed_from_u64(17);
+    for n in [50, 1000] {
+        let uniform: Vec<f64> = (0..n).map(|_| rng.gen::<f64>()).collect();
+        let result = SpeedOfLightHypothesisTest::shapiro_wilk(&uniform);
+        assert!(result.p_value < 0.05, "n {}: {:?}", n, result);
+    }
+}
+
+#[test]
+fn test_shapiro_wilk_small_and_degenerate_samples() {
+    // Three equally spaced points are as normal as three points can be
+    let result = SpeedOfLightHypothesisTest::shapiro_wilk(&[1.0, 2.0, 3.0]);
+    assert_close(result.w, 1.0, 1e-12);
+    assert_close(result.p_value, 1.0, 1e-9);
+
+    let too_many = seeded_normals(5001, 1);
+    for residuals in [&[1.0, 2.0][..], &[4.0; 10][..], &[0.0; 51][..], &too_many[..]] {
+        let result = SpeedOfLightHypothesisTest::shapiro_wilk(residuals);
+        assert!(result.w.is_nan() && result.p_value.is_nan(), "{:?}", result);
+    }
+}
+
+#[test]
+fn test_likelihood_ratio_favors_liv_for_dispersed_data() {
+    let data = dispersed_dataset(0.01, 0.01);
+    let result = SpeedOfLightHypothesisTest::likelihood_ratio_test(&data, None, None);
+    assert!(result.alternative_log_likelihood > result.null_log_likelihood);
+    assert_close(
+        result.test_statistic,
+        2.0 * (result.alternative_log_likelihood - result.null_log_likelihood),
+        1e-6,
+    );
+    assert!(result.p_value < 1e-6, "{:?}", result);
+    assert_close(result.slope, 0.01, 1e-4);
+    assert!(result.energy_scale.is_none());
+}
+
+#[test]
+fn test_likelihood_ratio_null_data_not_significant() {
+    let data = dispersed_dataset(0.0, 0.01);
+    let result = SpeedOfLightHypothesisTest::likelihood_ratio_test(&data, None, None);
+    assert!(result.p_value > 0.05, "{:?}", result);
+}
+
+#[test]
+fn test_likelihood_ratio_reports_energy_scale() {
+    let data = dispersed_dataset(0.01, 0.01);
+    // d/c = 1e17 s, slope 0.01 s/GeV → E_QG = 1e19 GeV
+    let distance = lightspeedvalidator::analyzer::SPEED_OF_LIGHT * 1.0e17;
+    let result = SpeedOfLightHypothesisTest::likelihood_ratio_test(&data, None, Some(distance));
+    let energy_scale = result.energy_scale.unwrap();
+    assert!((energy_scale / 1.0e19 - 1.0).abs() < 0.01, "{}", energy_scale);
+
+    let cosmology = lightspeedvalidator::Cosmology::planck_2018();
+    let with_redshift: Vec<TimingData> = data.iter().map(|m| m.clone().with_redshift(1.0)).collect();
+    let result = SpeedOfLightHypothesisTest::likelihood_ratio_test(&with_redshift, Some(&cosmology), None);
+    assert!(result.energy_scale.is_some());
+}
+
+#[test]
+fn test_f_test_favors_liv_for_dispersed_data() {
+    let data = dispersed_dataset(0.01, 0.01);
+    let result = SpeedOfLightHypothesisTest::f_test_model_comparison(&data, None, None);
+    assert_eq!(result.dof_num, 1);
+    assert_eq!(result.dof_den, data.len() - 2);
+    assert!(result.f_statistic > 100.0, "{:?}", result);
+    assert!(result.p_value < 1e-6, "{:?}", result);
+}
+
+#[test]
+fn test_f_test_null_data_not_significant() {
+    let data = dispersed_dataset(0.0, 0.01);
+    let result = SpeedOfLightHypothesisTest::f_test_model_comparison(&data, None, None);
+    assert!(result.p_value > 0.05, "{:?}", result);
+
+    let too_few = &data[..2];
+    let result = SpeedOfLightHypothesisTest::f_test_model_comparison(too_few, None, None);
+    assert_eq!(result.f_statistic, 0.0);
+    assert_eq!(result.p_value, 1.0);
+}
+
+#[test]
+fn test_f_test_removes_per_photon_travel_times() {
+    // Low-energy photons come from a source 100 s further away, which looks like
+    // an energy-dependent delay unless each photon's travel time is removed
+    let c = lightspeedvalidator::analyzer::SPEED_OF_LIGHT;
+    let data: Vec<TimingData> = dispersed_dataset(0.0, 0.01)
+        .into_iter()
+        .map(|m| {
+            let travel_time = if m.energy < 50.0 { 200.0 } else { 100.0 };
+            TimingData { arrival_time: m.arrival_time + travel_time, ..m }.with_distance(travel_time * c)
+        })
+        .collect();
+    let result = SpeedOfLightHypothesisTest::f_test_model_comparison(&data, None, None);
+    assert!(result.p_value > 0.05, "{:?}", result);
+
+    let shared: Vec<TimingData> = data
+        .iter()
+        .map(|m| TimingData { distance_m: None, ..m.clone() })
+        .collect();
+    let result = SpeedOfLightHypothesisTest::f_test_model_comparison(&shared, None, Some(100.0 * c));
+    assert!(result.p_value < 1e-6, "{:?}", result);
+}
+
+#[test]
+fn test_information_criteria_penalize_extra_parameter() {
+    let data = dispersed_dataset(0.0, 0.01)[..20].to_vec();
+    // The LIV model fits slightly better but pays for its extra parameter
+    let models = [("constant c", -10.0, 1), ("linear LIV", -9.6, 2)];
+    let results = SpeedOfLightHypothesisTest::information_criteria(&data, &models);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].model_name, "constant c");
+    assert_close(results[0].aic, 22.0, 1e-12);
+    assert_close(results[1].aic, 23.2, 1e-12);
+    assert_close(results[0].bic, 20.0_f64.ln() + 20.0, 1e-12);
+    assert_close(results[1].bic, 2.0 * 20.0_f64.ln() + 19.2, 1e-12);
+    assert_eq!(results[0].delta_aic, 0.0);
+    assert_close(results[1].delta_aic, 1.2, 1e-12);
+}
+
+#[test]
+fn test_information_criteria_prefer_liv_for_dispersed_data() {
+    let data = dispersed_dataset(0.01, 0.01);
+    let lrt = SpeedOfLightHypothesisTest::likelihood_ratio_test(&data, None, None);
+    let models = [
+        ("constant c", lrt.null_log_likelihood, 1),
+        ("linear LIV", lrt.alternative_log_likelihood, 2),
+    ];
+    let results = SpeedOfLightHypothesisTest::information_criteria(&data, &models);
+    assert_eq!(results[1].delta_aic, 0.0);
+    assert!(results[0].delta_aic > 10.0, "{:?}", results);
+    assert!(results[1].bic < results[0].bic);
+}
+
+#[test]
+fn test_mann_whitney_identical_samples() {
+    let sample = seeded_normals(40, 5);
+    let result = SpeedOfLightHypothesisTest::mann_whitney_u(&sample, &sample);
+    assert_close(result.u, 800.0, 1e-9);
+    assert_close(result.z, 0.0, 1e-12);
+    assert_close(result.p_value, 1.0, 1e-12);
+
+    let other = seeded_normals(40, 6);
+    let result = SpeedOfLightHypothesisTest::mann_whitney_u(&sample, &other);
+    assert!(result.p_value > 0.05, "{:?}", result);
+}
+
+#[test]
+fn test_mann_whitney_detects_shift() {
+    let low = seeded_normals(50, 7);
+    let high: Vec<f64> = seeded_normals(50, 8).iter().map(|t| t + 1.5).collect();
+    let result = SpeedOfLightHypothesisTest::mann_whitney_u(&low, &high);
+    assert!(result.z < 0.0, "{:?}", result);
+    assert!(result.p_value < 1e-6, "{:?}", result);
+}
+
+#[test]
+fn test_mann_whitney_counts_ties_as_half() {
+    let result = SpeedOfLightHypothesisTest::mann_whitney_u(&[1.0, 2.0, 2.0], &[2.0, 3.0]);
+    assert_close(result.u, 1.0, 1e-12);
+    let result = SpeedOfLightHypothesisTest::mann_whitney_u(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+    assert_eq!(result.u, 0.0);
+
+    let empty = SpeedOfLightHypothesisTest::mann_whitney_u(&[], &[1.0]);
+    assert_eq!(empty.p_value, 1.0);
+}
+
+#[test]
+fn test_two_sample_z_test() {
+    let same = SpeedOfLightHypothesisTest::two_sample_z_test((1.0, 0.1), (1.0, 0.2), 0.05).unwrap();
+    assert_eq!(same.z_score, 0.0);
+    assert!((same.p_value - 1.0).abs() < 1e-12);
+    assert!(same.is_consistent);
+
+    // Difference of 0.5 with error 0.25 is z = 2, two-sided p ≈ 0.0455
+    let apart = SpeedOfLightHypothesisTest::two_sample_z_test((0.5, 0.15), (0.0, 0.2), 0.05).unwrap();
+    assert!((apart.difference_error - 0.25).abs() < 1e-12);
+    assert!((apart.z_score - 2.0).abs() < 1e-12);
+    assert!((apart.p_value - 0.0455).abs() < 1e-4);
+    assert!(!apart.is_consistent);
+
+    assert!(SpeedOfLightHypothesisTest::two_sample_z_test((0.0, 0.0), (0.0, 1.0), 0.05).is_err());
+    assert!(SpeedOfLightHypothesisTest::two_sample_z_test((0.0, 1.0), (0.0, 1.0), 1.0).is_err());
+}
+
+#[test]
+fn test_combine_limits_of_consistent_sources() {
+    let sources = [(0.010, 0.004), (0.012, 0.003), (0.011, 0.005)];
+    let combined = SpeedOfLightHypothesisTest::combine_limits(&sources);
+    assert_eq!(combined.sources, 3);
+    assert_eq!(combined.degrees_of_freedom, 2);
+    // Weights 62500, 111111.1 and 40000 per s²/GeV²
+    let weights = [62500.0, 1.0e6 / 9.0, 40000.0];
+    let total: f64 = weights.iter().sum();
+    let expected = (0.010 * weights[0] + 0.012 * weights[1] + 0.011 * weights[2]) / total;
+    assert_close(combined.slope, expected, 1e-15);
+    assert_close(combined.slope_error, total.sqrt().recip(), 1e-15);
+    assert!(sources.iter().all(|&(_, error)| combined.slope_error < error));
+    assert!(combined.chi_squared < 1.0 && combined.p_value > 0.5, "{:?}", combined);
+}
+
+#[test]
+fn test_combine_limits_flags_inconsistent_source() {
+    let mut sources = vec![(0.010, 0.004), (0.012, 0.003), (0.011, 0.005)];
+    let consistent = SpeedOfLightHypothesisTest::combine_limits(&sources);
+    sources.push((0.050, 0.004));
+    let combined = SpeedOfLightHypothesisTest::combine_limits(&sources);
+    assert!(combined.chi_squared > 10.0 * consistent.chi_squared, "{:?}", combined);
+    assert!(combined.p_value < 1e-6, "{:?}", combined);
+
+    // Unusable entries are skipped; nothing usable leaves the slope undefined
+    sources.push((f64::NAN, 0.01));
+    sources.push((0.02, 0.0));
+    assert_eq!(SpeedOfLightHypothesisTest::combine_limits(&sources).sources, 4);
+    let empty = SpeedOfLightHypothesisTest::combine_limits(&[(0.02, -1.0)]);
+    assert!(empty.slope.is_nan() && empty.slope_error.is_infinite());
+    assert_eq!(empty.p_value, 1.0);
+}
+
+#[test]
+fn test_student_t_interval_wider_than_normal_for_small_sample() {
+    use lightspeedvalidator::CiMethod;
+
+    // Sample mean 2, sample variance 2/3, n = 4
+    let data: Vec<TimingData> = [1.0, 2.0, 3.0, 2.0]
+        .iter()
+        .map(|&t| measurement(1.0, t, 0.1))
+        .collect();
+    let interval = |method| SpeedOfLightHypothesisTest::confidence_interval(&data, 0.95, method);
+    let normal = interval(CiMethod::Normal).unwrap();
+    let t = interval(CiMethod::StudentT).unwrap();
+    assert_eq!(normal.method_used, "Normal approximation");
+    assert_eq!(t.method_used, "Student-t");
+
+    let half_width =
+        |ci: &lightspeedvalidator::ConfidenceInterval| (ci.upper_bound - ci.lower_bound) / 2.0;
+    let standard_error = (2.0f64 / 3.0 / 4.0).sqrt();
+    // z(0.975) = 1.959964, t(0.975, 3) = 3.182446
+    assert_close(half_width(&normal) / standard_error, 1.959964, 1e-5);
+    assert_close(half_width(&t) / standard_error, 3.182446, 1e-5);
+    assert!(half_width(&t) > 1.5 * half_width(&normal));
+    assert_close((t.upper_bound + t.lower_bound) / 2.0, 2.0, 1e-12);
+}
+
+#[test]
+fn test_confidence_interval_rejects_bad_input() {
+    use lightspeedvalidator::CiMethod;
+
+    let one = [measurement(1.0, 1.0, 0.1)];
+    assert!(SpeedOfLightHypothesisTest::confidence_interval(&one, 0.95, CiMethod::StudentT).is_err());
+    let two = [measurement(1.0, 1.0, 0.1), measurement(2.0, 2.0, 0.1)];
+    assert!(SpeedOfLightHypothesisTest::confidence_interval(&two, 1.0, CiMethod::Normal).is_err());
+}
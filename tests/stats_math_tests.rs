@@ -0,0 +1,127 @@
+// Reference values from standard tables, computed to double precision
+
+const TOLERANCE: f64 = 1e-8;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= TOLERANCE,
+        "expected {} within {}, got {}",
+        expected,
+        TOLERANCE,
+        actual
+    );
+}
+
+mod erf {
+    use super::assert_close;
+    use lightspeedvalidator::stats_math::erf;
+
+    #[test]
+    fn test_tabulated_values() {
+        assert_eq!(erf(0.0), 0.0);
+        assert_close(erf(0.5), 0.520_499_877_813_046_5);
+        assert_close(erf(1.0), 0.842_700_792_949_714_9);
+        assert_close(erf(2.0), 0.995_322_265_018_952_7);
+        assert_close(erf(3.5), 0.999_999_256_901_627_6);
+    }
+
+    #[test]
+    fn test_odd_symmetry() {
+        for &x in &[0.1, 0.7, 1.3, 2.9] {
+            assert_close(erf(-x), -erf(x));
+        }
+    }
+}
+
+mod normal_cdf {
+    use super::assert_close;
+    use lightspeedvalidator::stats_math::normal_cdf;
+
+    #[test]
+    fn test_tabulated_values() {
+        assert_close(normal_cdf(0.0), 0.5);
+        assert_close(normal_cdf(1.96), 0.975_002_104_851_779_6);
+        assert_close(normal_cdf(-1.0), 0.158_655_253_931_457_07);
+        assert_close(normal_cdf(-3.0), 0.001_349_898_031_630_103_5);
+    }
+}
+
+mod lower_incomplete_gamma {
+    use super::assert_close;
+    use lightspeedvalidator::stats_math::{erf, lower_incomplete_gamma};
+
+    #[test]
+    fn test_tabulated_values() {
+        // Integer shapes have the closed form 1 - e^-x Σ_{k<s} x^k / k!
+        assert_close(lower_incomplete_gamma(1.0, 2.0), 0.864_664_716_763_387_3);
+        assert_close(lower_incomplete_gamma(3.0, 2.0), 0.323_323_583_816_936_5);
+        assert_close(lower_incomplete_gamma(5.0, 10.0), 0.970_747_311_923_039);
+        assert_close(lower_incomplete_gamma(10.0, 3.0), 0.001_102_488_130_115_597_5);
+    }
+
+    #[test]
+    fn test_half_shape_matches_erf() {
+        for &x in &[0.25, 1.0, 4.0] {
+            assert_close(lower_incomplete_gamma(0.5, x * x), erf(x));
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(lower_incomplete_gamma(2.0, 0.0), 0.0);
+        assert_close(lower_incomplete_gamma(2.0, 200.0), 1.0);
+    }
+}
+
+mod gammaln {
+    use super::assert_close;
+    use lightspeedvalidator::stats_math::gammaln;
+
+    #[test]
+    fn test_tabulated_values() {
+        assert_close(gammaln(0.1), 2.252_712_651_734_205_5);
+        assert_close(gammaln(0.5), 0.572_364_942_924_700_4);
+        assert_close(gammaln(1.0), 0.0);
+        assert_close(gammaln(5.0), 3.178_053_830_347_945);
+        assert_close(gammaln(10.5), 13.940_625_219_403_763);
+    }
+
+    #[test]
+    fn test_factorial_recurrence() {
+        // ln Γ(x + 1) = ln Γ(x) + ln x
+        for &x in &[0.3, 2.5, 7.0] {
+            assert_close(gammaln(x + 1.0), gammaln(x) + f64::ln(x));
+        }
+    }
+}
+
+mod stable_sum {
+    use lightspeedvalidator::stats_math::stable_sum;
+
+    #[test]
+    fn test_recovers_terms_lost_by_naive_summation() {
+        // Each 1e-16 is below half an ulp of 1.0, so naive summation drops all of them
+        let values: Vec<f64> =
+            std::iter::once(1.0).chain(std::iter::repeat_n(1e-16, 1_000_000)).collect();
+        let naive: f64 = values.iter().sum();
+        assert_eq!(naive, 1.0);
+        let expected = 1.0 + 1e-10;
+        assert!((stable_sum(values.iter().copied()) - expected).abs() <= f64::EPSILON);
+    }
+
+    #[test]
+    fn test_cancellation_of_large_terms() {
+        // Naive summation returns 0; plain Kahan summation also fails here
+        let values = [1.0, 1e100, 1.0, -1e100];
+        assert_eq!(values.iter().sum::<f64>(), 0.0);
+        assert_eq!(stable_sum(values), 2.0);
+    }
+
+    #[test]
+    fn test_non_finite_values_propagate() {
+        assert_eq!(stable_sum([]), 0.0);
+        assert_eq!(stable_sum([1.0, f64::INFINITY, 2.0]), f64::INFINITY);
+        assert!(stable_sum([f64::INFINITY, f64::NEG_INFINITY]).is_nan());
+        assert!(stable_sum([1.0, f64::NAN]).is_nan());
+    }
+}